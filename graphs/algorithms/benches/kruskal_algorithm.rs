@@ -3,7 +3,7 @@
 extern crate test;
 
 use algorithms::calculate_min_total_weight;
-use graph::build_graph;
+use graph::{build_graph, Graph};
 use std::path::PathBuf;
 use test::Bencher;
 
@@ -11,24 +11,24 @@ use test::Bencher;
 
 #[bench]
 fn kruskal_bench_1000_nodes(b: &mut Bencher) {
-    let graph = build_graph(&PathBuf::from("benches/data/1000_nodes.txt")).unwrap();
+    let graph: Graph = build_graph(&PathBuf::from("benches/data/1000_nodes.txt")).unwrap();
     b.iter(|| calculate_min_total_weight(graph.clone()));
 }
 
 #[bench]
 fn kruskal_bench_10000_nodes(b: &mut Bencher) {
-    let graph = build_graph(&PathBuf::from("benches/data/10000_nodes.txt")).unwrap();
+    let graph: Graph = build_graph(&PathBuf::from("benches/data/10000_nodes.txt")).unwrap();
     b.iter(|| calculate_min_total_weight(graph.clone()));
 }
 
 #[bench]
 fn kruskal_bench_100000_nodes(b: &mut Bencher) {
-    let graph = build_graph(&PathBuf::from("benches/data/100000_nodes.txt")).unwrap();
+    let graph: Graph = build_graph(&PathBuf::from("benches/data/100000_nodes.txt")).unwrap();
     b.iter(|| calculate_min_total_weight(graph.clone()));
 }
 
 #[bench]
 fn kruskal_bench_200000_nodes(b: &mut Bencher) {
-    let graph = build_graph(&PathBuf::from("benches/data/200000_nodes.txt")).unwrap();
+    let graph: Graph = build_graph(&PathBuf::from("benches/data/200000_nodes.txt")).unwrap();
     b.iter(|| calculate_min_total_weight(graph.clone()));
 }
@@ -1,37 +1,37 @@
-use crate::errors::{AlgorithmResult, DijkstrasError};
-use graph::{adjacency_list, Graph};
+use crate::errors::{AlgorithmError, AlgorithmResult, DijkstrasError};
+use graph::{Graph, Weight};
 use std::cmp::{Ordering, PartialOrd};
 use std::collections::BinaryHeap;
 use utils::PositiveInteger;
 
 #[derive(PartialEq, Eq)]
-struct NodeDistance {
+struct NodeDistance<W> {
     /// node index
     index: u32,
 
     /// distance from start_node
-    distance: u32,
+    distance: W,
 }
 
-impl NodeDistance {
+impl<W: Weight> NodeDistance<W> {
     /// NodeDistance constructor
     ///
     /// # Arguments
     ///
     /// * `index` - node index
     /// * `distance` - distance from start index
-    pub fn new(index: u32, distance: u32) -> NodeDistance {
+    pub fn new(index: u32, distance: W) -> NodeDistance<W> {
         NodeDistance { index, distance }
     }
 }
 
-impl Ord for NodeDistance {
+impl<W: Weight> Ord for NodeDistance<W> {
     fn cmp(&self, other: &Self) -> Ordering {
         other.distance.cmp(&self.distance)
     }
 }
 
-impl PartialOrd for NodeDistance {
+impl<W: Weight> PartialOrd for NodeDistance<W> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
@@ -42,63 +42,163 @@ fn is_node_index_valid(index: u32, nodes_count: u32) -> bool {
 }
 
 /// Validates given nodes indexes and returns an error if at least one of them is greater than `nodes_count`
-fn validate_nodes(start_node: u32, end_node: u32, nodes_count: u32) -> Result<(), DijkstrasError> {
+fn validate_nodes<W: Weight>(graph: &Graph<W>, start_node: u32, end_node: u32) -> Result<(), DijkstrasError> {
+    let nodes_count = graph.nodes_count();
     if !is_node_index_valid(start_node, nodes_count) {
         return Err(DijkstrasError::InvalidStartNode {
-            start_node,
+            start_node: graph.node_name(start_node),
             nodes_count,
         });
     }
     if !is_node_index_valid(end_node, nodes_count) {
-        return Err(DijkstrasError::InvalidEndNode { end_node, nodes_count });
+        return Err(DijkstrasError::InvalidEndNode {
+            end_node: graph.node_name(end_node),
+            nodes_count,
+        });
     }
 
     Ok(())
 }
 
-pub fn find_shortest_path_length(
-    graph: &Graph,
+/// Returns an error if any edge of the graph has negative weight
+fn validate_weights<W: Weight>(graph: &Graph<W>) -> Result<(), DijkstrasError> {
+    match graph.negative_weight_edge() {
+        Some(edge) => Err(DijkstrasError::NegativeWeight {
+            edge: graph.describe_edge(edge),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Shortest path found by [`find_shortest_path`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShortestPath<W> {
+    /// Indices of consecutive nodes of the path (from start node to end node)
+    pub nodes: Vec<u32>,
+
+    /// Sum of weights of path edges
+    pub length: W,
+}
+
+/// Uses Dijkstra's algorithm to calculate length of the shortest path between two nodes
+///
+/// Returns [`DijkstrasError`] if one of the nodes doesn't exist, any edge of the graph has negative weight,
+/// there is no path between the nodes or length of a path overflows the weight type
+///
+/// # Arguments
+///
+/// * `graph` - graph with non-negative weights
+/// * `start_node` - index of the node, where path starts
+/// * `end_node` - index of the node, where path ends
+pub fn find_shortest_path_length<W: Weight>(
+    graph: &Graph<W>,
     start_node: PositiveInteger,
     end_node: PositiveInteger,
-) -> AlgorithmResult<u32> {
-    validate_nodes(start_node.value(), end_node.value(), graph.nodes_count)?;
+) -> AlgorithmResult<W> {
+    find_shortest_path(graph, start_node, end_node).map(|path| path.length)
+}
 
-    // create adjacency list describing given graph
-    let adjacency_list = adjacency_list(&graph.edges, graph.nodes_count);
+/// Uses Dijkstra's algorithm to find the shortest path between two nodes
+///
+/// Returns [`DijkstrasError`] if one of the nodes doesn't exist, any edge of the graph has negative weight,
+/// there is no path between the nodes or length of a path overflows the weight type
+///
+/// # Example
+/// ```
+/// use graph::Graph;
+/// use algorithms::find_shortest_path;
+/// use utils::PositiveInteger;
+///
+/// let graph: Graph = "3 3
+///     1 2 100
+///     2 3 150
+///     1 3 300"
+///     .parse()
+///     .unwrap();
+///
+/// let path = find_shortest_path(&graph, PositiveInteger::new(1), PositiveInteger::new(3)).unwrap();
+///
+/// assert_eq!(path.nodes, vec![1, 2, 3]);
+/// assert_eq!(path.length, 250);
+/// ```
+///
+/// # Arguments
+///
+/// * `graph` - graph with non-negative weights
+/// * `start_node` - index of the node, where path starts
+/// * `end_node` - index of the node, where path ends
+pub fn find_shortest_path<W: Weight>(
+    graph: &Graph<W>,
+    start_node: PositiveInteger,
+    end_node: PositiveInteger,
+) -> AlgorithmResult<ShortestPath<W>> {
+    validate_nodes(graph, start_node.value(), end_node.value())?;
+    validate_weights(graph)?;
 
     // create empty binary heap
     let mut heap = BinaryHeap::new();
 
-    // create vector of already calculated distances (initialized with usize::MAX)
-    // nodes are indexed from 1, so this vec has length adjacency_list.len() + 1
-    let mut calculated_distances: Vec<u32> = (0..=adjacency_list.len()).map(|_| u32::MAX).collect();
+    // create vector of already calculated distances (None means that node wasn't reached yet)
+    // nodes are indexed from 1, so this vec has length nodes_count + 1
+    let mut calculated_distances: Vec<Option<W>> = vec![None; graph.nodes_count() as usize + 1];
+
+    // node preceding every node on the shortest path found so far
+    let mut previous_nodes: Vec<u32> = vec![0; graph.nodes_count() as usize + 1];
 
     // starting node has distance == 0
-    calculated_distances[start_node.value() as usize] = 0;
-    heap.push(NodeDistance::new(start_node.value(), 0));
+    calculated_distances[start_node.value() as usize] = Some(W::zero());
+    heap.push(NodeDistance::new(start_node.value(), W::zero()));
 
     while let Some(popped_node) = heap.pop() {
         // if popped node is the one we are looking for
         if end_node == popped_node.index {
-            return Ok(popped_node.distance);
+            return Ok(ShortestPath {
+                nodes:  reconstruct_path(&previous_nodes, start_node.value(), end_node.value()),
+                length: popped_node.distance,
+            });
         }
 
         // if we already found a better way to popped node
-        if popped_node.distance > calculated_distances[popped_node.index as usize] {
+        if calculated_distances[popped_node.index as usize].is_some_and(|distance| popped_node.distance > distance) {
             continue;
         }
 
         // for every neighbour of popped_node see if we can find a shorter way
-        for (target_node, weight) in &adjacency_list[(popped_node.index as usize)] {
-            let new_distance = popped_node.distance + *weight as u32;
+        for (target_node, weight) in graph.neighbours(popped_node.index) {
+            let new_distance =
+                popped_node
+                    .distance
+                    .checked_add(weight)
+                    .ok_or_else(|| DijkstrasError::WeightOverflow {
+                        start_node: graph.node_name(start_node.value()),
+                        node:       graph.node_name(target_node),
+                    })?;
 
             // if way through popped_node is shorter
-            if new_distance < calculated_distances[*target_node] {
-                heap.push(NodeDistance::new(*target_node as u32, new_distance));
-                calculated_distances[*target_node] = new_distance;
+            if calculated_distances[target_node as usize].is_none_or(|distance| new_distance < distance) {
+                heap.push(NodeDistance::new(target_node, new_distance));
+                calculated_distances[target_node as usize] = Some(new_distance);
+                previous_nodes[target_node as usize] = popped_node.index;
             }
         }
     }
 
-    panic!("This shouldn't happen, because graph is connected");
+    Err(AlgorithmError::from(DijkstrasError::NoPath {
+        start_node: graph.node_name(start_node.value()),
+        end_node:   graph.node_name(end_node.value()),
+    }))
+}
+
+// follows previous nodes from the end node back to the start node
+fn reconstruct_path(previous_nodes: &[u32], start_node: u32, end_node: u32) -> Vec<u32> {
+    let mut path = vec![end_node];
+
+    let mut current_node = end_node;
+    while current_node != start_node {
+        current_node = previous_nodes[current_node as usize];
+        path.push(current_node);
+    }
+
+    path.reverse();
+    path
 }
@@ -17,7 +17,7 @@ pub type AlgorithmResult<T, E = AlgorithmError> = std::result::Result<T, E>;
 ///
 /// let actual_error = find_shortest_path_length(&graph, PositiveInteger::new(1), PositiveInteger::new(4)).unwrap_err();
 /// let expected_error = AlgorithmError::from(DijkstrasError::InvalidEndNode{
-/// end_node: 4,
+/// end_node: "4".to_owned(),
 /// nodes_count: 3,
 /// });
 ///
@@ -28,6 +28,10 @@ pub enum AlgorithmError {
     /// Error returned by Dijkstra's algorithm
     #[error("Dijkstra's algorithm error - {0}")]
     DijkstrasError(DijkstrasError),
+
+    /// Error returned by Kruskal's algorithm
+    #[error("Kruskal's algorithm error - {0}")]
+    KruskalsError(KruskalsError),
 }
 
 impl From<DijkstrasError> for AlgorithmError {
@@ -36,14 +40,20 @@ impl From<DijkstrasError> for AlgorithmError {
     }
 }
 
+impl From<KruskalsError> for AlgorithmError {
+    fn from(e: KruskalsError) -> Self {
+        Self::KruskalsError(e)
+    }
+}
+
 /// Errors returned by Dijkstra's algorithm
 #[derive(Error, Debug)]
 pub enum DijkstrasError {
     /// There is no node with given start index in the graph
     #[error("start_node `{start_node}` is greater than nodes_count `{nodes_count}`")]
     InvalidStartNode {
-        /// Given index of start node
-        start_node: u32,
+        /// Label of start node (or its index, if graph isn't labelled or has no such node)
+        start_node: String,
 
         /// Number of nodes in the graph
         nodes_count: u32,
@@ -52,10 +62,50 @@ pub enum DijkstrasError {
     /// There is no node with given end index in the graph
     #[error("end_node `{end_node}` is greater than nodes_count `{nodes_count}`")]
     InvalidEndNode {
-        /// Given index of end node
-        end_node: u32,
+        /// Label of end node (or its index, if graph isn't labelled or has no such node)
+        end_node: String,
 
         /// Number of nodes in the graph
         nodes_count: u32,
     },
+
+    /// Graph contains an edge with negative weight, Dijkstra's algorithm can't handle it
+    #[error("edge `{edge}` has negative weight, which isn't supported by Dijkstra's algorithm")]
+    NegativeWeight {
+        /// Edge with negative weight, written the same way as in graph description file
+        /// (see [`graph::Graph::describe_edge`])
+        edge: String,
+    },
+
+    /// End node can't be reached from start node
+    #[error("there is no path from `{start_node}` to `{end_node}`")]
+    NoPath {
+        /// Label of start node (or its index, if graph isn't labelled)
+        start_node: String,
+
+        /// Label of end node (or its index, if graph isn't labelled)
+        end_node: String,
+    },
+
+    /// Length of a path doesn't fit in the weight type
+    #[error("length of the path from `{start_node}` to `{node}` overflows the weight type")]
+    WeightOverflow {
+        /// Label of start node (or its index, if graph isn't labelled)
+        start_node: String,
+
+        /// Label of the last node of the path (or its index, if graph isn't labelled)
+        node: String,
+    },
+}
+
+/// Errors returned by Kruskal's algorithm
+#[derive(Error, Debug)]
+pub enum KruskalsError {
+    /// Spanning tree exists only for connected graphs (edges of directed graph are treated as undirected)
+    #[error("graph is not connected, so it has no spanning tree")]
+    GraphNotConnected,
+
+    /// Total weight of the spanning tree doesn't fit in the weight type
+    #[error("total weight of the spanning tree overflows the weight type")]
+    WeightOverflow,
 }
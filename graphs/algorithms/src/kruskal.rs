@@ -1,108 +1,80 @@
-use graph::Graph;
-use std::cmp::Ordering;
+use crate::errors::{AlgorithmError, AlgorithmResult, KruskalsError};
+use graph::{Graph, IncrementalComponents, Weight};
 
-#[derive(Debug, Clone)]
-pub struct UnionFind {
-    parent: Vec<u32>,
-    rank:   Vec<u8>,
-}
-
-impl UnionFind {
-    /// n - count of disjoint sets.
-    pub fn new(n: u32) -> Self {
-        let rank = vec![0; (n + 1) as usize];
-        let mut parent = Vec::with_capacity(n as usize);
-
-        for i in 0..=n {
-            parent.push(i);
-        }
-
-        UnionFind { parent, rank }
-    }
-
-    /// find parent of element with given index
-    pub fn find_parent(&self, index: u32) -> u32 {
-        assert!(index < self.parent.len() as u32);
-
-        let mut current_index = index;
-        while self.parent[current_index as usize] != current_index {
-            current_index = self.parent[current_index as usize];
-        }
-        current_index
-    }
-
-    /// `find_parent` with path compression
-    pub fn find_mut_parent(&mut self, index: u32) -> u32 {
-        assert!(index < self.parent.len() as u32);
-
-        let mut index = index;
-
-        let mut parent = self.parent[index as usize];
-        while parent != index {
-            let grandparent = self.parent[parent as usize];
-            self.parent[index as usize] = grandparent;
-            index = parent;
-            parent = grandparent;
-        }
-        index
-    }
-
-    /// call `union` method for parents of given elements
-    pub fn merge_parents(&mut self, x: u32, y: u32) -> bool {
-        let x_parent = self.find_parent(x);
-        let y_parent = self.find_parent(y);
-        if x_parent != y_parent {
-            self.union(x_parent, y_parent)
-        } else {
-            false
-        }
-    }
-
-    /// merge two sets
-    pub fn union(&mut self, x: u32, y: u32) -> bool {
-        if x == y {
-            return false;
-        }
-        let xrep = self.find_mut_parent(x);
-        let yrep = self.find_mut_parent(y);
-
-        if xrep == yrep {
-            return false;
-        }
+/// Minimum spanning tree found by [`find_min_spanning_tree`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpanningTree<W> {
+    /// Indices of tree edges in [`graph::Graph::edges`], ordered by weight
+    pub edges: Vec<usize>,
 
-        let xrepu = xrep as usize;
-        let yrepu = yrep as usize;
-
-        let xrank = self.rank[xrepu];
-        let yrank = self.rank[yrepu];
-
-        match xrank.cmp(&yrank) {
-            Ordering::Less => self.parent[xrepu] = yrep,
-            Ordering::Greater => self.parent[yrepu] = xrep,
-            Ordering::Equal => {
-                self.parent[yrepu] = xrep;
-                self.rank[xrepu] += 1;
-            }
-        }
-
-        true
-    }
+    /// Sum of weights of tree edges
+    pub total_weight: W,
 }
 
 /// Uses Kruskal's algorithm to calculate weight of graph minimum spanning tree
 ///
+/// Edges with negative weights are handled correctly.
+/// Returns [`KruskalsError::GraphNotConnected`] if graph isn't connected
+/// and [`KruskalsError::WeightOverflow`] if total weight doesn't fit in `W` (see [`find_min_spanning_tree`]).
+///
 /// # Arguments
 ///
 /// * 'graph' - connected graph that will be used to calculate weight of minimum spanning tree
-pub fn calculate_min_total_weight(mut graph: Graph) -> i32 {
-    let mut union_find = UnionFind::new(graph.nodes_count);
+pub fn calculate_min_total_weight<W: Weight>(graph: Graph<W>) -> AlgorithmResult<W> {
+    find_min_spanning_tree(&graph).map(|tree| tree.total_weight)
+}
 
-    graph.edges.sort_by(|a, b| a.weight.cmp(&b.weight));
+/// Uses Kruskal's algorithm to find edges of graph minimum spanning tree
+///
+/// Edges of directed graph are treated as undirected.
+/// Returns [`KruskalsError::GraphNotConnected`] if graph isn't connected, so there is no spanning tree,
+/// and [`KruskalsError::WeightOverflow`] if total weight of the tree doesn't fit in `W`.
+///
+/// # Example
+/// ```
+/// use graph::Graph;
+/// use algorithms::find_min_spanning_tree;
+///
+/// let graph: Graph = "3 3
+///     1 2 100
+///     2 3 150
+///     1 3 120"
+///     .parse()
+///     .unwrap();
+///
+/// let tree = find_min_spanning_tree(&graph).unwrap();
+///
+/// assert_eq!(tree.edges, vec![0, 2]);
+/// assert_eq!(tree.total_weight, 220);
+/// ```
+///
+/// # Arguments
+///
+/// * 'graph' - connected graph that will be used to find minimum spanning tree
+pub fn find_min_spanning_tree<W: Weight>(graph: &Graph<W>) -> AlgorithmResult<SpanningTree<W>> {
+    let mut components = IncrementalComponents::new(graph.nodes_count());
+
+    let mut positions: Vec<usize> = (0..graph.edges().len()).collect();
+    positions.sort_by_key(|position| graph.edges()[*position].weight);
+
+    let edges: Vec<usize> = positions
+        .into_iter()
+        .filter(|position| {
+            let edge = &graph.edges()[*position];
+            components.add_edge(edge.from_index, edge.to_index)
+        })
+        .collect();
+
+    if components.count() > 1 {
+        return Err(AlgorithmError::from(KruskalsError::GraphNotConnected));
+    }
 
-    graph
-        .edges
+    let total_weight = edges
         .iter()
-        .filter(|e| union_find.merge_parents(e.from_index, e.to_index))
-        .map(|e| e.weight as i32)
-        .sum()
+        .try_fold(W::zero(), |total, position| {
+            total.checked_add(graph.edges()[*position].weight)
+        })
+        .ok_or(KruskalsError::WeightOverflow)?;
+
+    Ok(SpanningTree { edges, total_weight })
 }
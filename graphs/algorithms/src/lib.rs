@@ -2,7 +2,8 @@
 //!
 //! # Algorithms
 //!
-//! * Kruskal's algorithm ([`calculate_min_total_weight`])
+//! * Kruskal's algorithm ([`calculate_min_total_weight`], [`find_min_spanning_tree`])
+//! * Dijkstra's algorithm ([`find_shortest_path_length`], [`find_shortest_path`])
 //!
 //! # Example
 //! ```
@@ -17,7 +18,7 @@
 //!     .parse()
 //!     .unwrap();
 //!
-//! let minimum_spanning_tree_weight = calculate_min_total_weight(graph);
+//! let minimum_spanning_tree_weight = calculate_min_total_weight(graph).unwrap();
 //!
 //! assert_eq!(minimum_spanning_tree_weight, 170);
 //! ```
@@ -29,6 +30,6 @@ extern crate test_case;
 mod dijkstra;
 mod errors;
 mod kruskal;
-pub use dijkstra::find_shortest_path_length;
+pub use dijkstra::{find_shortest_path, find_shortest_path_length, ShortestPath};
 pub use errors::*;
-pub use kruskal::calculate_min_total_weight;
+pub use kruskal::{calculate_min_total_weight, find_min_spanning_tree, SpanningTree};
@@ -1,19 +1,21 @@
-use graph::{build_graph, Graph};
+use graph::{build_graph_with_options, Graph, ReadOptions, ValidationPolicy, Weight};
 use std::path::PathBuf;
 
 // -----------------------------------------------------------------------------
 
-fn build_graph_from_dataset_number(dataset_number: u32) -> Graph {
+// algorithms check their own preconditions, so graphs don't have to be connected
+fn build_graph_from_dataset_number<W: Weight>(dataset_number: u32) -> Graph<W> {
     let mut path = PathBuf::from("tests/data");
     path.push(format!("passing{}", dataset_number));
     path.set_extension("txt");
 
-    build_graph(&path).unwrap()
+    build_graph_with_options(&path, ReadOptions::new().with_policy(ValidationPolicy::permissive())).unwrap()
 }
 
 mod kruskal {
     use super::*;
-    use algorithms::calculate_min_total_weight;
+    use algorithms::{calculate_min_total_weight, find_min_spanning_tree, AlgorithmError, KruskalsError};
+    use graph::OrderedFloat;
     use test_case::test_case;
 
     #[test_case(1 => 280)]
@@ -24,22 +26,59 @@ mod kruskal {
     #[test_case(6 => 2700)]
     #[test_case(7 => 1500)]
     #[test_case(8 => 400)]
+    #[test_case(10 => 280)]
+    #[test_case(13 => 20)]
     fn passing(dataset_number: u32) -> i32 {
         let graph = build_graph_from_dataset_number(dataset_number);
 
-        calculate_min_total_weight(graph)
+        calculate_min_total_weight(graph).unwrap()
+    }
+
+    #[test_case(1 => vec![0, 5, 1, 2])]
+    #[test_case(13 => vec![1, 0, 3])]
+    fn passing_tree_edges(dataset_number: u32) -> Vec<usize> {
+        let graph: Graph = build_graph_from_dataset_number(dataset_number);
+
+        find_min_spanning_tree(&graph).unwrap().edges
+    }
+
+    #[test_case(11 => OrderedFloat(4.25))]
+    fn passing_float(dataset_number: u32) -> OrderedFloat {
+        let graph = build_graph_from_dataset_number(dataset_number);
+
+        calculate_min_total_weight(graph).unwrap()
+    }
+
+    #[test_case(12 => 7_000_000_000)]
+    fn passing_u64(dataset_number: u32) -> u64 {
+        let graph = build_graph_from_dataset_number(dataset_number);
+
+        calculate_min_total_weight(graph).unwrap()
+    }
+
+    #[test_case(15, KruskalsError::GraphNotConnected)]
+    #[test_case(16, KruskalsError::WeightOverflow)]
+    fn failing(dataset_number: u32, expected_error: KruskalsError) {
+        let graph: Graph = build_graph_from_dataset_number(dataset_number);
+        let actual_error = calculate_min_total_weight(graph).unwrap_err();
+
+        assert_eq!(
+            actual_error.to_string(),
+            AlgorithmError::from(expected_error).to_string()
+        )
     }
 }
 
 mod dijkstra {
     use super::*;
     use algorithms::DijkstrasError;
-    use algorithms::{find_shortest_path_length, AlgorithmError};
+    use algorithms::{find_shortest_path, find_shortest_path_length, AlgorithmError};
+    use graph::OrderedFloat;
     use test_case::test_case;
     use utils::PositiveInteger;
 
     fn test_error(dataset_number: u32, start_node: u32, end_node: u32, expected_error: AlgorithmError) {
-        let graph = build_graph_from_dataset_number(dataset_number);
+        let graph: Graph = build_graph_from_dataset_number(dataset_number);
         let actual_error =
             find_shortest_path_length(&graph, PositiveInteger::new(start_node), PositiveInteger::new(end_node))
                 .unwrap_err();
@@ -56,18 +95,92 @@ mod dijkstra {
     #[test_case(3, 1, 8 => 350)]
     #[test_case(6, 1, 3 => 400)]
     #[test_case(6, 5, 2 => 300)]
-    fn passing(dataset_number: u32, start_node: u32, end_node: u32) -> u32 {
+    #[test_case(9, 1, 4 => 120)]
+    #[test_case(9, 5, 4 => 130)]
+    #[test_case(9, 4, 1 => 170)]
+    #[test_case(14, 1, 4 => 180)]
+    #[test_case(15, 4, 5 => 70)]
+    #[test_case(14, 4, 2 => 80)]
+    fn passing(dataset_number: u32, start_node: u32, end_node: u32) -> i32 {
         let graph = build_graph_from_dataset_number(dataset_number);
         find_shortest_path_length(&graph, PositiveInteger::new(start_node), PositiveInteger::new(end_node)).unwrap()
     }
 
+    #[test_case(11, 1, 4 => OrderedFloat(4.25))]
+    #[test_case(11, 1, 3 => OrderedFloat(3.75))]
+    #[test_case(11, 4, 2 => OrderedFloat(2.75))]
+    fn passing_float(dataset_number: u32, start_node: u32, end_node: u32) -> OrderedFloat {
+        let graph = build_graph_from_dataset_number(dataset_number);
+        find_shortest_path_length(&graph, PositiveInteger::new(start_node), PositiveInteger::new(end_node)).unwrap()
+    }
+
+    #[test_case(1, 1, 4 => vec![1, 2, 4])]
+    #[test_case(1, 3, 3 => vec![3])]
+    #[test_case(3, 1, 8 => vec![1, 2, 5, 8])]
+    fn passing_path(dataset_number: u32, start_node: u32, end_node: u32) -> Vec<u32> {
+        let graph: Graph = build_graph_from_dataset_number(dataset_number);
+        let path =
+            find_shortest_path(&graph, PositiveInteger::new(start_node), PositiveInteger::new(end_node)).unwrap();
+
+        let path_length = path
+            .nodes
+            .windows(2)
+            .map(|step| {
+                graph
+                    .neighbours(step[0])
+                    .filter(|(node, _)| *node == step[1])
+                    .map(|(_, weight)| weight)
+                    .min()
+                    .unwrap()
+            })
+            .sum::<i32>();
+        assert_eq!(path_length, path.length);
+
+        path.nodes
+    }
+
+    #[test_case(10, "gate", "pond_north" => 50)]
+    #[test_case(10, "gate", "pond_south" => 120)]
+    #[test_case(10, "garage", "pond_south" => 160)]
+    fn passing_labelled(dataset_number: u32, start_label: &str, end_label: &str) -> i32 {
+        let graph = build_graph_from_dataset_number(dataset_number);
+        let labels = graph.labels().unwrap();
+
+        let start_node = PositiveInteger::new(labels.index(start_label).unwrap());
+        let end_node = PositiveInteger::new(labels.index(end_label).unwrap());
+
+        find_shortest_path_length(&graph, start_node, end_node).unwrap()
+    }
+
     #[test_case(1, 6, 1, DijkstrasError::InvalidStartNode {
-        start_node: 6,
+        start_node: "6".to_owned(),
         nodes_count: 5,
     })]
     #[test_case(7, 6, 7, DijkstrasError::InvalidEndNode {
-    end_node: 7,
-    nodes_count: 6,
+        end_node: "7".to_owned(),
+        nodes_count: 6,
+    })]
+    #[test_case(17, 5, 1, DijkstrasError::InvalidStartNode {
+        start_node: "5".to_owned(),
+        nodes_count: 4,
+    })]
+    #[test_case(13, 1, 4, DijkstrasError::NegativeWeight {
+        edge: "2 3 -5".to_owned(),
+    })]
+    #[test_case(15, 1, 5, DijkstrasError::NoPath {
+        start_node: "1".to_owned(),
+        end_node: "5".to_owned(),
+    })]
+    #[test_case(16, 1, 3, DijkstrasError::WeightOverflow {
+        start_node: "1".to_owned(),
+        node: "3".to_owned(),
+    })]
+    #[test_case(17, 1, 3, DijkstrasError::NoPath {
+        start_node: "gate".to_owned(),
+        end_node: "pond_north".to_owned(),
+    })]
+    #[test_case(18, 1, 3, DijkstrasError::NegativeWeight {
+        edge: "gate fountain -5".to_owned(),
     })]
     fn failing(dataset_number: u32, start_node: u32, end_node: u32, expected_error: DijkstrasError) {
         test_error(
@@ -1,14 +1,217 @@
-use crate::Edge;
+use crate::{Edge, GraphKind, Weight};
+use std::fmt;
+use std::iter::Zip;
+use std::slice::Iter;
+use std::sync::OnceLock;
 
-pub type AdjacencyList = Vec<Vec<(usize, usize)>>;
+/// Compact (compressed sparse row) adjacency structure of the graph
+///
+/// Neighbours of all nodes are kept in two flat vectors (`targets` and `weights`),
+/// `offsets[index]..offsets[index + 1]` is the range of neighbours of node with given `index`.
+///
+/// Nodes are indexed from 1, so node 0 has never any neighbours.
+#[derive(Debug, Clone)]
+pub struct AdjacencyList<W = i32> {
+    /// Start of neighbours of every node in `targets` and `weights` (contains `nodes_count + 2` values)
+    offsets: Vec<usize>,
 
-pub fn adjacency_list(edges: &[Edge], nodes_count: u32) -> AdjacencyList {
-    let mut adjacency_list: AdjacencyList = vec![Vec::new(); (nodes_count + 1) as usize];
+    /// Indices of neighbours
+    targets: Vec<u32>,
 
-    for edge in edges {
-        adjacency_list[edge.from_index as usize].push((edge.to_index as usize, edge.weight as usize));
-        adjacency_list[edge.to_index as usize].push((edge.from_index as usize, edge.weight as usize));
+    /// Weights of edges leading to neighbours
+    weights: Vec<W>,
+}
+
+impl<W: Weight> AdjacencyList<W> {
+    /// Creates adjacency structure from edges of the graph
+    ///
+    /// Edge of undirected graph is added to neighbours of both nodes it connects
+    ///
+    /// # Arguments
+    ///
+    /// * `edges` - array slice of graph edges
+    /// * `nodes_count` - number of nodes in the graph
+    /// * `kind` - [`crate::GraphKind`] of the graph
+    pub fn new(edges: &[Edge<W>], nodes_count: u32, kind: GraphKind) -> AdjacencyList<W> {
+        let arcs = || {
+            let reversed_arcs = edges
+                .iter()
+                .filter(move |_| kind == GraphKind::Undirected)
+                .map(|edge| (edge.to_index, edge.from_index, edge.weight));
+
+            edges
+                .iter()
+                .map(|edge| (edge.from_index, edge.to_index, edge.weight))
+                .chain(reversed_arcs)
+        };
+
+        Self::from_arcs(nodes_count, arcs)
+    }
+
+    /// Creates adjacency structure of the directed graph with every edge reversed
+    ///
+    /// # Arguments
+    ///
+    /// * `edges` - array slice of graph edges
+    /// * `nodes_count` - number of nodes in the graph
+    pub fn reversed(edges: &[Edge<W>], nodes_count: u32) -> AdjacencyList<W> {
+        let arcs = || edges.iter().map(|edge| (edge.to_index, edge.from_index, edge.weight));
+
+        Self::from_arcs(nodes_count, arcs)
+    }
+
+    // `arcs` is called twice - first to count neighbours of every node, then to fill the vectors
+    fn from_arcs<F, I>(nodes_count: u32, arcs: F) -> AdjacencyList<W>
+    where
+        F: Fn() -> I,
+        I: Iterator<Item = (u32, u32, W)>,
+    {
+        let mut offsets = vec![0; nodes_count as usize + 2];
+
+        for (from_index, _, _) in arcs() {
+            offsets[from_index as usize + 1] += 1;
+        }
+
+        for index in 1..offsets.len() {
+            offsets[index] += offsets[index - 1];
+        }
+
+        let arcs_count = offsets[offsets.len() - 1];
+        let mut targets = vec![0; arcs_count];
+        let mut weights = vec![W::zero(); arcs_count];
+
+        // next free position in `targets` and `weights` for every node
+        let mut positions = offsets.clone();
+
+        for (from_index, to_index, weight) in arcs() {
+            let position = &mut positions[from_index as usize];
+            targets[*position] = to_index;
+            weights[*position] = weight;
+            *position += 1;
+        }
+
+        AdjacencyList {
+            offsets,
+            targets,
+            weights,
+        }
+    }
+
+    /// Number of nodes in the graph
+    pub fn nodes_count(&self) -> u32 {
+        (self.offsets.len() - 2) as u32
+    }
+
+    /// Returns iterator over pairs (`neighbour_index`, `weight`) of node with given index
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - index of the node
+    pub fn neighbours(&self, index: u32) -> Neighbours<'_, W> {
+        let range = self.offsets[index as usize]..self.offsets[index as usize + 1];
+
+        Neighbours {
+            inner: self.targets[range.clone()].iter().zip(self.weights[range].iter()),
+        }
+    }
+}
+
+/// Iterator over neighbours of the node, returned by [`AdjacencyList::neighbours`]
+///
+/// Yields pairs (`neighbour_index`, `weight`)
+#[derive(Clone)]
+pub struct Neighbours<'a, W = i32> {
+    inner: Zip<Iter<'a, u32>, Iter<'a, W>>,
+}
+
+impl<'a, W: Weight> Iterator for Neighbours<'a, W> {
+    type Item = (u32, W);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(target, weight)| (*target, *weight))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, W: Weight> ExactSizeIterator for Neighbours<'a, W> {}
+
+// -----------------------------------------------------------------------------
+
+/// [`AdjacencyList`] built on first use and kept inside [`crate::Graph`]
+#[derive(Clone)]
+pub(crate) struct AdjacencyCache<W>(OnceLock<AdjacencyList<W>>);
+
+impl<W> Default for AdjacencyCache<W> {
+    fn default() -> Self {
+        AdjacencyCache(OnceLock::new())
+    }
+}
+
+impl<W> AdjacencyCache<W> {
+    pub fn get_or_init<F>(&self, f: F) -> &AdjacencyList<W>
+    where
+        F: FnOnce() -> AdjacencyList<W>,
+    {
+        self.0.get_or_init(f)
     }
 
-    adjacency_list
+    // adjacency structure will be built again on next use
+    pub fn invalidate(&mut self) {
+        self.0.take();
+    }
+}
+
+// cache isn't a part of the graph data, so it's skipped when graph is printed
+impl<W> fmt::Debug for AdjacencyCache<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("..")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edges() -> Vec<Edge> {
+        vec![Edge::new(1, 2, 100), Edge::new(3, 1, 50), Edge::new(1, 3, 70)]
+    }
+
+    #[test]
+    fn undirected() {
+        let adjacency_list = AdjacencyList::new(&edges(), 3, GraphKind::Undirected);
+
+        assert_eq!(adjacency_list.nodes_count(), 3);
+        assert_eq!(adjacency_list.neighbours(0).count(), 0);
+        assert_eq!(adjacency_list.neighbours(1).collect::<Vec<_>>(), vec![
+            (2, 100),
+            (3, 70),
+            (3, 50)
+        ]);
+        assert_eq!(adjacency_list.neighbours(2).collect::<Vec<_>>(), vec![(1, 100)]);
+        assert_eq!(adjacency_list.neighbours(3).collect::<Vec<_>>(), vec![(1, 50), (1, 70)]);
+    }
+
+    #[test]
+    fn directed() {
+        let adjacency_list = AdjacencyList::new(&edges(), 3, GraphKind::Directed);
+
+        assert_eq!(adjacency_list.neighbours(1).collect::<Vec<_>>(), vec![
+            (2, 100),
+            (3, 70)
+        ]);
+        assert_eq!(adjacency_list.neighbours(2).count(), 0);
+        assert_eq!(adjacency_list.neighbours(3).collect::<Vec<_>>(), vec![(1, 50)]);
+    }
+
+    #[test]
+    fn reversed() {
+        let adjacency_list = AdjacencyList::reversed(&edges(), 3);
+
+        assert_eq!(adjacency_list.neighbours(1).collect::<Vec<_>>(), vec![(3, 50)]);
+        assert_eq!(adjacency_list.neighbours(2).collect::<Vec<_>>(), vec![(1, 100)]);
+        assert_eq!(adjacency_list.neighbours(3).collect::<Vec<_>>(), vec![(1, 70)]);
+    }
 }
@@ -0,0 +1,553 @@
+use crate::{AttributeError, EdgeId, EditGraphError, EditGraphResult, Graph, NodeId, Weight};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::btree_map::{BTreeMap, IntoIter};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::iter::FromIterator;
+use std::str::FromStr;
+
+/// Keyword starting a node line in graph description, e.g. `node 2 color=red`
+pub(crate) const NODE_LINE_START: &str = "node";
+
+/// Value of node or edge attribute
+///
+/// In graph description value is `true` or `false`, integer or floating-point number (checked in this order),
+/// anything else is text. Text that would be read as another value is written in quotes (e.g. `"12"`).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(untagged))]
+pub enum AttributeValue {
+    /// `true` or `false`
+    Bool(bool),
+
+    /// Integer number
+    Integer(i64),
+
+    /// Floating-point number
+    Float(f64),
+
+    /// Any other value
+    Text(String),
+}
+
+impl AttributeValue {
+    /// Returns the value if it's [`AttributeValue::Bool`]
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            AttributeValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it's [`AttributeValue::Integer`]
+    pub fn as_integer(&self) -> Option<i64> {
+        match self {
+            AttributeValue::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it's [`AttributeValue::Float`]
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            AttributeValue::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it's [`AttributeValue::Text`]
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            AttributeValue::Text(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    // text in quotes is always text, otherwise the first type the token can be parsed to is chosen
+    fn parse(token: &str) -> Option<AttributeValue> {
+        if let Some(text) = unquoted(token) {
+            return decoded(text).map(AttributeValue::Text);
+        }
+
+        Some(match token {
+            "true" => AttributeValue::Bool(true),
+            "false" => AttributeValue::Bool(false),
+            _ => match (token.parse::<i64>(), token.parse::<f64>()) {
+                (Ok(value), _) => AttributeValue::Integer(value),
+                (_, Ok(value)) => AttributeValue::Float(value),
+                _ => AttributeValue::Text(decoded(token)?),
+            },
+        })
+    }
+}
+
+/// Value is written as a single token of graph description
+impl Display for AttributeValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            AttributeValue::Bool(value) => write!(f, "{}", value),
+            AttributeValue::Integer(value) => write!(f, "{}", value),
+            // debug format always contains a dot or an exponent, so the value isn't read as integer
+            AttributeValue::Float(value) => write!(f, "{:?}", value),
+            AttributeValue::Text(text) => {
+                let encoded = encoded(text);
+                match AttributeValue::parse(&encoded) {
+                    Some(AttributeValue::Text(_)) if !encoded.is_empty() => write!(f, "{}", encoded),
+                    _ => write!(f, "\"{}\"", encoded),
+                }
+            }
+        }
+    }
+}
+
+impl From<bool> for AttributeValue {
+    fn from(value: bool) -> Self {
+        AttributeValue::Bool(value)
+    }
+}
+
+impl From<i32> for AttributeValue {
+    fn from(value: i32) -> Self {
+        AttributeValue::Integer(value.into())
+    }
+}
+
+impl From<i64> for AttributeValue {
+    fn from(value: i64) -> Self {
+        AttributeValue::Integer(value)
+    }
+}
+
+impl From<f64> for AttributeValue {
+    fn from(value: f64) -> Self {
+        AttributeValue::Float(value)
+    }
+}
+
+impl From<&str> for AttributeValue {
+    fn from(value: &str) -> Self {
+        AttributeValue::Text(value.to_owned())
+    }
+}
+
+impl From<String> for AttributeValue {
+    fn from(value: String) -> Self {
+        AttributeValue::Text(value)
+    }
+}
+
+/// Attributes of a node or an edge, mapping names to [`AttributeValue`]s
+///
+/// In graph description attributes are written as `name=value` tokens after the weight of the edge,
+/// or after the node in a node line `node <index or label> name=value ...`. Whitespace, `#`, `%`, `"` and `=`
+/// in names and text values are written as `%` followed by two hexadecimal digits of every byte (e.g. `%20`).
+///
+/// # Example
+/// ```
+/// use graph::{AttributeValue, Graph};
+///
+/// let graph: Graph = "3 2
+///     1 2 100 lanes=2 name=Main%20Street
+///     2 3 80 toll=true
+///     node 3 height=12.5"
+///     .parse()
+///     .unwrap();
+///
+/// let main_street = graph.edge_attributes(graph.edge_id(0).unwrap()).unwrap();
+///
+/// assert_eq!(main_street.get("lanes"), Some(&AttributeValue::Integer(2)));
+/// assert_eq!(main_street.get("name").and_then(AttributeValue::as_text), Some("Main Street"));
+///
+/// let node = graph.node_attributes(graph.node_id(3).unwrap()).unwrap();
+///
+/// assert_eq!(node.get("height").and_then(AttributeValue::as_float), Some(12.5));
+/// assert_eq!(graph.to_string().parse::<Graph>().unwrap(), graph);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+pub struct Attributes(BTreeMap<String, AttributeValue>);
+
+impl Attributes {
+    /// Creates empty attributes
+    pub fn new() -> Attributes {
+        Attributes::default()
+    }
+
+    /// Returns value of the attribute (or None if there is no such attribute)
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - name of the attribute
+    pub fn get(&self, name: &str) -> Option<&AttributeValue> {
+        self.0.get(name)
+    }
+
+    /// Sets value of the attribute and returns the previous one
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - name of the attribute
+    /// * `value` - new value of the attribute
+    pub fn insert<N, V>(&mut self, name: N, value: V) -> Option<AttributeValue>
+    where
+        N: Into<String>,
+        V: Into<AttributeValue>,
+    {
+        self.0.insert(name.into(), value.into())
+    }
+
+    /// Removes the attribute and returns its value
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - name of the attribute
+    pub fn remove(&mut self, name: &str) -> Option<AttributeValue> {
+        self.0.remove(name)
+    }
+
+    /// Returns iterator over pairs (`name`, `value`) ordered by names
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &AttributeValue)> {
+        self.0.iter().map(|(name, value)| (name.as_str(), value))
+    }
+
+    /// Returns number of the attributes
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Checks if there are no attributes
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<N: Into<String>, V: Into<AttributeValue>> FromIterator<(N, V)> for Attributes {
+    fn from_iter<I: IntoIterator<Item = (N, V)>>(iter: I) -> Self {
+        Attributes(
+            iter.into_iter()
+                .map(|(name, value)| (name.into(), value.into()))
+                .collect(),
+        )
+    }
+}
+
+impl<N: Into<String>, V: Into<AttributeValue>> Extend<(N, V)> for Attributes {
+    fn extend<I: IntoIterator<Item = (N, V)>>(&mut self, iter: I) {
+        self.0
+            .extend(iter.into_iter().map(|(name, value)| (name.into(), value.into())));
+    }
+}
+
+impl IntoIterator for Attributes {
+    type IntoIter = IntoIter<String, AttributeValue>;
+    type Item = (String, AttributeValue);
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// Attributes are written as space separated `name=value` tokens, the same way as in graph description
+impl Display for Attributes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        for (position, (name, value)) in self.0.iter().enumerate() {
+            if position > 0 {
+                write!(f, " ")?;
+            }
+
+            if name.is_empty() {
+                write!(f, "\"\"={}", value)?;
+            } else {
+                write!(f, "{}={}", encoded(name), value)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Attributes are parsed from whitespace separated `name=value` tokens, empty string gives empty attributes
+impl FromStr for Attributes {
+    type Err = AttributeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut attributes = Attributes::new();
+
+        for token in s.split_whitespace() {
+            let invalid = || AttributeError::InvalidAttribute(token.to_owned());
+
+            let (name, value) = token.split_once('=').ok_or_else(invalid)?;
+            if name.is_empty() {
+                return Err(invalid());
+            }
+
+            let name = decoded(unquoted(name).unwrap_or(name)).ok_or_else(invalid)?;
+            let value = AttributeValue::parse(value).ok_or_else(invalid)?;
+
+            attributes.0.insert(name, value);
+        }
+
+        Ok(attributes)
+    }
+}
+
+/// Attributes of nodes and edges
+///
+/// Attributes are kept for [`NodeId`] and [`EdgeId`] handles, so they stay with their node or edge
+/// when the graph is edited, and they are removed together with it. Subgraphs and transformed graphs
+/// don't have attributes.
+impl<W: Weight> Graph<W> {
+    /// Returns attributes of the node (or None if it has no attributes or it was removed)
+    ///
+    /// # Arguments
+    ///
+    /// * `node` - handle of the node
+    pub fn node_attributes(&self, node: NodeId) -> Option<&Attributes> {
+        self.node_attributes
+            .get(&node)
+            .filter(|attributes| !attributes.is_empty())
+    }
+
+    /// Returns attributes of the node for changing, or [`EditGraphError::UnknownNode`] if there is no such node
+    ///
+    /// # Example
+    /// ```
+    /// use graph::Graph;
+    ///
+    /// let mut graph: Graph = "2 1
+    ///     1 2 100"
+    ///     .parse()
+    ///     .unwrap();
+    ///
+    /// let gate = graph.node_id(1).unwrap();
+    /// graph.node_attributes_mut(gate).unwrap().insert("color", "red");
+    ///
+    /// assert_eq!(graph.to_string(), "2 1\n1 2 100\nnode 1 color=red\n");
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `node` - handle of the node
+    pub fn node_attributes_mut(&mut self, node: NodeId) -> EditGraphResult<&mut Attributes> {
+        self.node_index(node).ok_or(EditGraphError::UnknownNode(node))?;
+
+        Ok(self.node_attributes.entry(node).or_default())
+    }
+
+    /// Returns attributes of the edge (or None if it has no attributes or it was removed)
+    ///
+    /// # Arguments
+    ///
+    /// * `edge` - handle of the edge
+    pub fn edge_attributes(&self, edge: EdgeId) -> Option<&Attributes> {
+        self.edge_attributes
+            .get(&edge)
+            .filter(|attributes| !attributes.is_empty())
+    }
+
+    /// Returns attributes of the edge for changing, or [`EditGraphError::UnknownEdge`] if there is no such edge
+    ///
+    /// # Arguments
+    ///
+    /// * `edge` - handle of the edge
+    pub fn edge_attributes_mut(&mut self, edge: EdgeId) -> EditGraphResult<&mut Attributes> {
+        self.edge(edge).ok_or(EditGraphError::UnknownEdge(edge))?;
+
+        Ok(self.edge_attributes.entry(edge).or_default())
+    }
+
+    // attributes of the node with given index, used by serializers
+    pub(crate) fn node_attributes_at(&self, index: u32) -> Option<&Attributes> {
+        self.node_id(index).and_then(|node| self.node_attributes(node))
+    }
+
+    // attributes of the edge at given position in `edges`, used by serializers
+    pub(crate) fn edge_attributes_at(&self, position: usize) -> Option<&Attributes> {
+        self.edge_id(position).and_then(|edge| self.edge_attributes(edge))
+    }
+
+    // attributes are compared by indices and positions, because handles of equal graphs might differ
+    pub(crate) fn attributes_eq(&self, other: &Graph<W>) -> bool {
+        let no_attributes = |graph: &Graph<W>| graph.node_attributes.is_empty() && graph.edge_attributes.is_empty();
+        if no_attributes(self) && no_attributes(other) {
+            return true;
+        }
+
+        (1..=self.nodes_count).all(|index| self.node_attributes_at(index) == other.node_attributes_at(index))
+            && (0..self.edges.len())
+                .all(|position| self.edge_attributes_at(position) == other.edge_attributes_at(position))
+    }
+}
+
+// splits line into the first `fields_count` tokens describing an edge or a node and attributes following them,
+// attributes start only with `name=value` token, otherwise the whole line is the description
+pub(crate) fn split_attributes(line: &str, fields_count: usize) -> (&str, &str) {
+    let mut rest = line;
+    for _ in 0..fields_count {
+        let token_start = rest.trim_start();
+        rest = &token_start[token_start.find(char::is_whitespace).unwrap_or(token_start.len())..];
+    }
+
+    let attributes = rest.trim_start();
+    match attributes.split_whitespace().next() {
+        Some(token) if token.contains('=') => (line[..line.len() - rest.len()].trim_end(), attributes),
+        _ => (line, ""),
+    }
+}
+
+// node line contains `node` keyword, the node and at least one attribute
+pub(crate) fn is_node_line(line: &str) -> bool {
+    let mut tokens = line.split_whitespace();
+    tokens.next() == Some(NODE_LINE_START) && tokens.nth(1).is_some_and(|token| token.contains('='))
+}
+
+// text between quotes, if the token is quoted
+fn unquoted(token: &str) -> Option<&str> {
+    token.strip_prefix('"').and_then(|token| token.strip_suffix('"'))
+}
+
+// characters that can't be written directly in a token are written as `%XX` for every byte
+fn encoded(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_whitespace() || c.is_control() || matches!(c, '#' | '%' | '"' | '=') {
+            let mut bytes = [0; 4];
+            for byte in c.encode_utf8(&mut bytes).bytes() {
+                encoded.push_str(&format!("%{:02X}", byte));
+            }
+        } else {
+            encoded.push(c);
+        }
+    }
+
+    encoded
+}
+
+// reverses `encoded`, None if `%` isn't followed by two hexadecimal digits or bytes aren't valid UTF-8
+fn decoded(token: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(token.len());
+    let mut rest = token.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let digits = std::str::from_utf8(tail.get(..2)?).ok()?;
+            bytes.push(u8::from_str_radix(digits, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BuildGraphError;
+    use test_case::test_case;
+
+    #[test_case("true" => AttributeValue::Bool(true); "bool")]
+    #[test_case("-12" => AttributeValue::Integer(-12); "integer")]
+    #[test_case("1e3" => AttributeValue::Float(1000.0); "float")]
+    #[test_case("\"12\"" => AttributeValue::from("12"); "quoted number")]
+    #[test_case("Main%20Street" => AttributeValue::from("Main Street"); "escaped text")]
+    fn parsing_value(token: &str) -> AttributeValue {
+        AttributeValue::parse(token).unwrap()
+    }
+
+    #[test_case(AttributeValue::from("12"); "text looking like number")]
+    #[test_case(AttributeValue::from("false"); "text looking like bool")]
+    #[test_case(AttributeValue::from(""); "empty text")]
+    #[test_case(AttributeValue::from("a=b #1 \"50%\"\n"); "text with special characters")]
+    #[test_case(AttributeValue::from(2.0); "float with integer value")]
+    fn value_round_trip(value: AttributeValue) {
+        assert_eq!(AttributeValue::parse(&value.to_string()), Some(value));
+    }
+
+    #[test_case("color"; "missing value")]
+    #[test_case("=red"; "missing name")]
+    #[test_case("color=%2"; "invalid escape")]
+    fn invalid_attribute(token: &str) {
+        let expected = AttributeError::InvalidAttribute(token.to_owned());
+        let actual = token.parse::<Attributes>().unwrap_err();
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test_case("1 2 100 a=1", 3 => ("1 2 100", "a=1"); "edge with attributes")]
+    #[test_case("1 2 100", 3 => ("1 2 100", ""); "edge without attributes")]
+    #[test_case("1 2 100 x a=1", 3 => ("1 2 100 x a=1", ""); "trailing token")]
+    #[test_case("node gate  a=1 b=2", 2 => ("node gate", "a=1 b=2"); "node line")]
+    fn splitting_attributes(line: &str, fields_count: usize) -> (&str, &str) {
+        split_attributes(line, fields_count)
+    }
+
+    #[test]
+    fn labelled_graph_round_trip() -> crate::BuildGraphResult<()> {
+        let input = "3 2 labels
+            gate pond 100 lit=true
+            pond fountain 50
+            node fountain name=\"42\" depth=1.5
+            node gate width=3";
+        let graph: Graph = input.parse()?;
+
+        let gate = graph.node_id(1).unwrap();
+        assert_eq!(
+            graph.node_attributes(gate),
+            Some(&Attributes::from_iter([("width", 3)]))
+        );
+        assert_eq!(graph.edge_attributes(graph.edge_id(1).unwrap()), None);
+
+        let description = graph.to_string();
+        assert_eq!(
+            description,
+            "3 2 labels
+gate pond 100 lit=true
+pond fountain 50
+node gate width=3
+node fountain depth=1.5 name=\"42\"
+"
+        );
+        assert_eq!(description.parse::<Graph>()?, graph);
+        Ok(())
+    }
+
+    #[test]
+    fn attributes_follow_handles() -> EditGraphResult<()> {
+        let mut graph: Graph = "3 2\n1 2 100 name=first\n2 3 80 name=second\nnode 3 color=red"
+            .parse()
+            .unwrap();
+
+        let first_edge = graph.edge_id(0).unwrap();
+        let second_edge = graph.edge_id(1).unwrap();
+        let last_node = graph.node_id(3).unwrap();
+
+        graph.remove_edge(first_edge)?;
+        graph.remove_node(graph.node_id(1).unwrap())?;
+
+        assert_eq!(graph.edge_attributes(first_edge), None);
+        assert_eq!(
+            graph
+                .edge_attributes(second_edge)
+                .and_then(|attributes| attributes.get("name")),
+            Some(&AttributeValue::from("second"))
+        );
+        assert_eq!(graph.node_index(last_node), Some(2));
+        assert_eq!(graph.to_string(), "2 1\n1 2 80 name=second\nnode 2 color=red\n");
+        Ok(())
+    }
+
+    #[test]
+    fn node_line_with_unknown_node() {
+        let expected = BuildGraphError::ErrorInGraphDescriptionFile {
+            line_no: 3,
+            error:   Box::new(BuildGraphError::from(AttributeError::UnknownNode {
+                node:        "4".to_owned(),
+                nodes_count: 3,
+            })),
+        };
+        let actual = "3 2\n1 2 100\nnode 4 color=red\n2 3 80".parse::<Graph>().unwrap_err();
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+}
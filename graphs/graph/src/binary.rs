@@ -0,0 +1,525 @@
+use crate::labels::node_labels;
+use crate::{
+    AttributeValue,
+    Attributes,
+    BinaryFormatError,
+    BuildGraphError,
+    BuildGraphResult as Result,
+    Edge,
+    Graph,
+    GraphBuilder,
+    GraphKind,
+    GraphParameters,
+    ValidationPolicy,
+    Weight,
+};
+use crc32fast::Hasher;
+use std::convert::{TryFrom, TryInto};
+use std::io::{self, Write};
+
+/// Bytes starting every binary graph file
+const MAGIC: &[u8; 4] = b"GRPH";
+
+/// Version of the format written by [`write_binary`]
+const VERSION: u16 = 1;
+
+/// Number of bytes before the first edge (magic, version, kind, weight tag, flags, nodes and edges count)
+const HEADER_SIZE: usize = 4 + 2 + 1 + 1 + 1 + 4 + 8;
+
+/// Number of bytes of the checksum at the end of the input
+const CHECKSUM_SIZE: usize = 4;
+
+/// Flag telling that the graph is labelled
+const LABELLED_FLAG: u8 = 0b1;
+
+/// Flag telling that the graph has node or edge attributes
+const ATTRIBUTES_FLAG: u8 = 0b10;
+
+/// Flag telling that the graph isn't connected, so connectivity isn't checked while reading it
+const DISCONNECTED_FLAG: u8 = 0b100;
+
+/// Type tags of attribute values
+const BOOL_TAG: u8 = 0;
+const INTEGER_TAG: u8 = 1;
+const FLOAT_TAG: u8 = 2;
+const TEXT_TAG: u8 = 3;
+
+/// Checks if the input starts with magic bytes of binary graph format
+pub(crate) fn has_magic(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+/// Writes graph in compact binary format, which is much faster to load than text formats
+///
+/// All numbers are little-endian. The input consists of:
+///
+/// * header - magic bytes `GRPH`, format version (`u16`), graph kind (`u8`, 0 - undirected, 1 - directed),
+///   weight type tag ([`Weight::BINARY_TAG`], `u8`), flags (`u8`, bit 0 - labelled, bit 1 - attributes,
+///   bit 2 - disconnected), `nodes_count` (`u32`) and number of edges (`u64`)
+/// * edges - `from_index` (`u32`), `to_index` (`u32`) and weight (see [`Weight::write_binary`])
+/// * labels (only if the graph is labelled) - number of labels (`u32`), then length (`u32`) and UTF-8 bytes of each label
+/// * attributes (only if the graph has them) - number of nodes with attributes (`u32`), then index of each node (`u32`)
+///   and its attributes, number of edges with attributes (`u64`), then position of each edge (`u64`) and its attributes.
+///   Attributes are their number (`u32`), then name (written as a label), value type (`u8`, 0 - bool, 1 - integer,
+///   2 - float, 3 - text) and value (`u8`, `i64`, `f64` or text written as a label) of each attribute
+/// * CRC-32 checksum (`u32`) of everything before it
+///
+/// Graph is read back with [`crate::build_graph`] (format is detected) or [`crate::build_graph_in_format`]
+/// with [`crate::GraphFormat::Binary`]. Weight type of the read graph must be the same as of the written one.
+///
+/// # Example
+/// ```
+/// use graph::{build_graph, write_binary, Graph};
+///
+/// let graph: Graph = "3 2
+///     1 2 100
+///     2 3 150"
+///     .parse()
+///     .unwrap();
+///
+/// let mut output = vec![];
+/// write_binary(&graph, &mut output).unwrap();
+///
+/// let read_graph: Graph = build_graph(output.as_slice()).unwrap();
+///
+/// assert_eq!(read_graph.edges(), graph.edges());
+/// ```
+///
+/// # Arguments
+///
+/// * `graph` - graph that will be written
+/// * `writer` - destination of the output
+pub fn write_binary<W: Weight, Wr: Write>(graph: &Graph<W>, writer: Wr) -> io::Result<()> {
+    let mut writer = ChecksumWriter::new(writer);
+
+    let node_attributes = (1..=graph.nodes_count)
+        .filter_map(|index| Some((index, graph.node_attributes_at(index)?)))
+        .collect::<Vec<_>>();
+    let edge_attributes = (0..graph.edges.len())
+        .filter_map(|position| Some((position, graph.edge_attributes_at(position)?)))
+        .collect::<Vec<_>>();
+
+    let mut flags = if graph.labels().is_some() { LABELLED_FLAG } else { 0 };
+    if !node_attributes.is_empty() || !edge_attributes.is_empty() {
+        flags |= ATTRIBUTES_FLAG;
+    }
+    if !graph.is_connected() {
+        flags |= DISCONNECTED_FLAG;
+    }
+
+    writer.write_all(MAGIC)?;
+    writer.write_all(&VERSION.to_le_bytes())?;
+    writer.write_all(&[kind_byte(graph.kind), W::BINARY_TAG, flags])?;
+    writer.write_all(&graph.nodes_count.to_le_bytes())?;
+    writer.write_all(&(graph.edges.len() as u64).to_le_bytes())?;
+
+    let mut buffer = vec![];
+    for edge in &graph.edges {
+        buffer.clear();
+        buffer.extend_from_slice(&edge.from_index.to_le_bytes());
+        buffer.extend_from_slice(&edge.to_index.to_le_bytes());
+        edge.weight.write_binary(&mut buffer);
+
+        writer.write_all(&buffer)?;
+    }
+
+    if let Some(labels) = graph.labels() {
+        writer.write_all(&(labels.len() as u32).to_le_bytes())?;
+
+        for (_, label) in labels.iter() {
+            writer.write_all(&(label.len() as u32).to_le_bytes())?;
+            writer.write_all(label.as_bytes())?;
+        }
+    }
+
+    if flags & ATTRIBUTES_FLAG != 0 {
+        writer.write_all(&(node_attributes.len() as u32).to_le_bytes())?;
+        for (index, attributes) in node_attributes {
+            buffer.clear();
+            buffer.extend_from_slice(&index.to_le_bytes());
+            write_attributes(attributes, &mut buffer);
+
+            writer.write_all(&buffer)?;
+        }
+
+        writer.write_all(&(edge_attributes.len() as u64).to_le_bytes())?;
+        for (position, attributes) in edge_attributes {
+            buffer.clear();
+            buffer.extend_from_slice(&(position as u64).to_le_bytes());
+            write_attributes(attributes, &mut buffer);
+
+            writer.write_all(&buffer)?;
+        }
+    }
+
+    writer.finish()
+}
+
+/// Builds a graph from input written by [`write_binary`]
+///
+/// Checksum is verified before anything else is read, then edges are added with [`GraphBuilder::add_edge`],
+/// so the graph is validated the same way as graphs read from text formats.
+pub(crate) fn build_graph_from_binary<W: Weight>(bytes: &[u8], policy: ValidationPolicy) -> Result<Graph<W>> {
+    if !has_magic(bytes) {
+        return Err(BuildGraphError::from(BinaryFormatError::InvalidMagic));
+    }
+    if bytes.len() < HEADER_SIZE + CHECKSUM_SIZE {
+        return Err(BuildGraphError::from(BinaryFormatError::UnexpectedEnd));
+    }
+
+    let mut reader = BinaryReader::new(&bytes[MAGIC.len()..]);
+
+    let version = reader.u16()?;
+    if version != VERSION {
+        return Err(BuildGraphError::from(BinaryFormatError::UnsupportedVersion(version)));
+    }
+
+    let (content, checksum) = bytes.split_at(bytes.len() - CHECKSUM_SIZE);
+    let expected = u32::from_le_bytes(checksum.try_into().expect("checksum has 4 bytes"));
+    let actual = crc32fast::hash(content);
+    if expected != actual {
+        return Err(BuildGraphError::from(BinaryFormatError::ChecksumMismatch {
+            expected,
+            actual,
+        }));
+    }
+
+    let mut reader = BinaryReader::new(&content[MAGIC.len() + 2..]);
+
+    let kind = match reader.u8()? {
+        0 => GraphKind::Undirected,
+        1 => GraphKind::Directed,
+        byte => return Err(BuildGraphError::from(BinaryFormatError::InvalidGraphKind(byte))),
+    };
+
+    let weight_tag = reader.u8()?;
+    if weight_tag != W::BINARY_TAG {
+        return Err(BuildGraphError::from(BinaryFormatError::WeightTypeMismatch {
+            expected: W::BINARY_TAG,
+            found:    weight_tag,
+        }));
+    }
+
+    let flags = reader.u8()?;
+    let nodes_count = reader.u32()?;
+
+    // every edge takes at least 8 bytes, so a bigger count can't be valid (and shouldn't be allocated)
+    let edges_count = reader.u64()?;
+    if edges_count > (reader.remaining() / 8) as u64 {
+        return Err(BuildGraphError::from(BinaryFormatError::UnexpectedEnd));
+    }
+
+    let policy = policy.with_connectivity(policy.require_connected && flags & DISCONNECTED_FLAG == 0);
+    let mut graph_builder = GraphBuilder::new(GraphParameters::new(nodes_count, edges_count as usize).with_kind(kind))
+        .with_validation_policy(policy);
+    for position in 0..edges_count as usize {
+        let from_index = reader.u32()?;
+        let to_index = reader.u32()?;
+        let weight = reader.weight(position)?;
+
+        graph_builder.add_edge(Edge::new(from_index, to_index, weight))?;
+    }
+
+    let labels = if flags & LABELLED_FLAG != 0 {
+        let labels_count = reader.u32()?;
+        let labels = (1..=labels_count)
+            .map(|index| reader.label(index))
+            .collect::<Result<Vec<_>>>()?;
+
+        Some(node_labels(labels, nodes_count)?)
+    } else {
+        None
+    };
+
+    if flags & ATTRIBUTES_FLAG != 0 {
+        let nodes_count = reader.u32()?;
+        for _ in 0..nodes_count {
+            let index = reader.u32()?;
+            let attributes = reader.attributes(|| format!("node {}", index))?;
+
+            graph_builder.set_node_attributes(index, attributes)?;
+        }
+
+        let edges_count = reader.u64()?;
+        for _ in 0..edges_count {
+            let position = reader.u64()?;
+            let attributes = reader.attributes(|| format!("edge {}", position))?;
+
+            graph_builder.set_edge_attributes(usize::try_from(position).unwrap_or(usize::MAX), attributes)?;
+        }
+    }
+
+    if reader.remaining() > 0 {
+        return Err(BuildGraphError::from(BinaryFormatError::UnexpectedTrailingBytes));
+    }
+
+    let graph = graph_builder.build()?;
+
+    Ok(match labels {
+        Some(labels) => graph.with_labels(labels),
+        None => graph,
+    })
+}
+
+// names and text values are written the same way as labels
+fn write_attributes(attributes: &Attributes, buffer: &mut Vec<u8>) {
+    let write_text = |text: &str, buffer: &mut Vec<u8>| {
+        buffer.extend_from_slice(&(text.len() as u32).to_le_bytes());
+        buffer.extend_from_slice(text.as_bytes());
+    };
+
+    buffer.extend_from_slice(&(attributes.len() as u32).to_le_bytes());
+    for (name, value) in attributes.iter() {
+        write_text(name, buffer);
+
+        match value {
+            AttributeValue::Bool(value) => buffer.extend_from_slice(&[BOOL_TAG, *value as u8]),
+            AttributeValue::Integer(value) => {
+                buffer.push(INTEGER_TAG);
+                buffer.extend_from_slice(&value.to_le_bytes());
+            }
+            AttributeValue::Float(value) => {
+                buffer.push(FLOAT_TAG);
+                buffer.extend_from_slice(&value.to_le_bytes());
+            }
+            AttributeValue::Text(text) => {
+                buffer.push(TEXT_TAG);
+                write_text(text, buffer);
+            }
+        }
+    }
+}
+
+fn kind_byte(kind: GraphKind) -> u8 {
+    match kind {
+        GraphKind::Undirected => 0,
+        GraphKind::Directed => 1,
+    }
+}
+
+// updates the checksum with every written byte and appends it in `finish`
+struct ChecksumWriter<Wr> {
+    writer: Wr,
+    hasher: Hasher,
+}
+
+impl<Wr: Write> ChecksumWriter<Wr> {
+    fn new(writer: Wr) -> ChecksumWriter<Wr> {
+        ChecksumWriter {
+            writer,
+            hasher: Hasher::new(),
+        }
+    }
+
+    fn write_all(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.hasher.update(bytes);
+        self.writer.write_all(bytes)
+    }
+
+    fn finish(mut self) -> io::Result<()> {
+        let checksum = self.hasher.finalize();
+        self.writer.write_all(&checksum.to_le_bytes())?;
+        self.writer.flush()
+    }
+}
+
+// reads consecutive values from the input, running out of bytes is an error
+struct BinaryReader<'b> {
+    bytes: &'b [u8],
+}
+
+impl<'b> BinaryReader<'b> {
+    fn new(bytes: &'b [u8]) -> BinaryReader<'b> {
+        BinaryReader { bytes }
+    }
+
+    fn remaining(&self) -> usize {
+        self.bytes.len()
+    }
+
+    fn take(&mut self, count: usize) -> Result<&'b [u8]> {
+        if count > self.bytes.len() {
+            return Err(BuildGraphError::from(BinaryFormatError::UnexpectedEnd));
+        }
+
+        let (taken, rest) = self.bytes.split_at(count);
+        self.bytes = rest;
+
+        Ok(taken)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().expect("2 bytes are taken")))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().expect("4 bytes are taken")))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().expect("8 bytes are taken")))
+    }
+
+    // `position` of the edge is used only in the error
+    fn weight<W: Weight>(&mut self, position: usize) -> Result<W> {
+        let (weight, size) = W::read_binary(self.bytes)
+            .ok_or_else(|| BuildGraphError::from(BinaryFormatError::InvalidWeight(position)))?;
+        self.take(size)?;
+
+        Ok(weight)
+    }
+
+    // `index` of the node is used only in the error
+    fn label(&mut self, index: u32) -> Result<String> {
+        let length = self.u32()? as usize;
+        let bytes = self.take(length)?;
+
+        String::from_utf8(bytes.to_vec()).map_err(|_| BuildGraphError::from(BinaryFormatError::InvalidLabel(index)))
+    }
+
+    // `owner` describes the node or edge in the error
+    fn attributes<F: Fn() -> String>(&mut self, owner: F) -> Result<Attributes> {
+        let invalid = || BuildGraphError::from(BinaryFormatError::InvalidAttribute(owner()));
+        let text = |reader: &mut Self| -> Result<String> {
+            let length = reader.u32()? as usize;
+            String::from_utf8(reader.take(length)?.to_vec()).map_err(|_| invalid())
+        };
+
+        let mut attributes = Attributes::new();
+        for _ in 0..self.u32()? {
+            let name = text(self)?;
+            let value = match self.u8()? {
+                BOOL_TAG => AttributeValue::Bool(self.u8()? != 0),
+                INTEGER_TAG => AttributeValue::Integer(self.u64()? as i64),
+                FLOAT_TAG => AttributeValue::Float(f64::from_bits(self.u64()?)),
+                TEXT_TAG => AttributeValue::Text(text(self)?),
+                _ => return Err(invalid()),
+            };
+
+            attributes.insert(name, value);
+        }
+
+        Ok(attributes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{build_graph, build_graph_in_format, build_graph_with_options, GraphFormat, OrderedFloat, ReadOptions};
+
+    fn binary<W: Weight>(graph: &Graph<W>) -> Vec<u8> {
+        let mut output = vec![];
+        write_binary(graph, &mut output).unwrap();
+        output
+    }
+
+    #[test]
+    fn labelled_round_trip() -> Result<()> {
+        let graph: Graph = "3 3 directed labels
+            gate pond 100
+            pond fountain -50
+            fountain gate 70"
+            .parse()?;
+
+        let read_graph: Graph = build_graph_in_format(binary(&graph).as_slice(), GraphFormat::Binary)?;
+
+        assert_eq!(read_graph.nodes_count, graph.nodes_count);
+        assert_eq!(read_graph.kind, graph.kind);
+        assert_eq!(read_graph.labels(), graph.labels());
+        assert_eq!(read_graph.edges, graph.edges);
+
+        Ok(())
+    }
+
+    #[test]
+    fn disconnected_round_trip() -> Result<()> {
+        let graph: Graph = build_graph_with_options(
+            "4 2\n1 2 10\n3 4 20",
+            ReadOptions::new().with_policy(ValidationPolicy::permissive()),
+        )?;
+
+        let read_graph: Graph = build_graph(binary(&graph).as_slice())?;
+
+        assert_eq!(read_graph, graph);
+
+        Ok(())
+    }
+
+    #[test]
+    fn float_round_trip() -> Result<()> {
+        let graph: Graph<OrderedFloat> = "2 1
+            1 2 12.5"
+            .parse()?;
+
+        let read_graph: Graph<OrderedFloat> = build_graph_from_binary(&binary(&graph), ValidationPolicy::default())?;
+
+        assert_eq!(read_graph.edges, graph.edges);
+
+        Ok(())
+    }
+
+    #[test]
+    fn corrupted_edge() -> Result<()> {
+        let graph: Graph = "2 1
+            1 2 100"
+            .parse()?;
+
+        let mut bytes = binary(&graph);
+        bytes[HEADER_SIZE] = 2;
+
+        let actual = build_graph_from_binary::<i32>(&bytes, ValidationPolicy::default()).unwrap_err();
+        assert!(matches!(
+            actual,
+            BuildGraphError::BinaryFormatError(BinaryFormatError::ChecksumMismatch { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn different_weight_type() -> Result<()> {
+        let graph: Graph = "2 1
+            1 2 100"
+            .parse()?;
+
+        let actual = build_graph_from_binary::<u64>(&binary(&graph), ValidationPolicy::default()).unwrap_err();
+        let expected = BuildGraphError::from(BinaryFormatError::WeightTypeMismatch {
+            expected: u64::BINARY_TAG,
+            found:    i32::BINARY_TAG,
+        });
+
+        assert_eq!(actual.to_string(), expected.to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_magic() {
+        let actual = build_graph_in_format::<i32, _>("2 1\n1 2 100", GraphFormat::Binary).unwrap_err();
+
+        assert_eq!(
+            actual.to_string(),
+            BuildGraphError::from(BinaryFormatError::InvalidMagic).to_string()
+        );
+    }
+
+    #[test]
+    fn attributes_round_trip() -> Result<()> {
+        let graph: Graph = "3 2 labels
+            gate pond 100 name=Main%20Street lanes=2
+            pond fountain 80 toll=true
+            node fountain height=12.5 lit=false"
+            .parse()?;
+
+        let read_graph: Graph = build_graph_in_format(binary(&graph).as_slice(), GraphFormat::Binary)?;
+
+        assert_eq!(read_graph, graph);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,330 @@
+use crate::{Edge, Graph, Weight};
+
+/// Connected components of the graph, computed by [`Graph::connected_components`]
+/// or [`IncrementalComponents::components`]
+///
+/// Components are identified by numbers from 0 to `count() - 1`, ordered by the smallest node index
+/// in the component. Members of every component are sorted by index.
+///
+/// # Example
+/// ```
+/// use graph::{build_graph_with_options, Graph, ReadOptions, ValidationPolicy};
+///
+/// // default policy rejects disconnected graphs
+/// let graph: Graph = build_graph_with_options("5 2
+///     1 4 100
+///     3 5 80", ReadOptions::new().with_policy(ValidationPolicy::permissive()))
+///     .unwrap();
+///
+/// let components = graph.connected_components();
+///
+/// assert_eq!(components.count(), 3);
+/// assert_eq!(components.component_of(4), Some(0));
+/// assert_eq!(components.component_of(5), Some(2));
+/// assert_eq!(components.members(1), Some(&[2][..]));
+/// assert_eq!(components.sizes(), vec![2, 1, 2]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Components {
+    // component id of node with index `i` at position `i - 1`
+    ids:     Vec<u32>,
+    members: Vec<Vec<u32>>,
+}
+
+impl Components {
+    /// Returns number of components
+    pub fn count(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Returns id of the component containing node with given index (None if there is no such node)
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - index of the node
+    pub fn component_of(&self, index: u32) -> Option<u32> {
+        self.ids.get((index as usize).checked_sub(1)?).copied()
+    }
+
+    /// Returns ids of components of all nodes, id of node with index `i` is at position `i - 1`
+    pub fn component_ids(&self) -> &[u32] {
+        &self.ids
+    }
+
+    /// Returns sorted indices of nodes in the component (None if there is no such component)
+    ///
+    /// # Arguments
+    ///
+    /// * `component` - id of the component
+    pub fn members(&self, component: u32) -> Option<&[u32]> {
+        self.members.get(component as usize).map(Vec::as_slice)
+    }
+
+    /// Returns sizes of components, size of component `c` is at position `c`
+    pub fn sizes(&self) -> Vec<usize> {
+        self.members.iter().map(Vec::len).collect()
+    }
+
+    /// Returns iterator over members of components, in order of their ids
+    pub fn iter(&self) -> impl Iterator<Item = &[u32]> {
+        self.members.iter().map(Vec::as_slice)
+    }
+}
+
+/// Connected components of the graph growing edge by edge, backed by union-find
+///
+/// Every node starts in its own component, adding an edge joins components of its ends.
+/// Adding an edge or node and checking component of the node take almost constant time.
+/// Direction of the edges is ignored, so in directed graph components are weakly connected.
+///
+/// Methods taking node index panic if there is no node with this index (nodes are indexed from 1).
+///
+/// # Example
+/// ```
+/// use graph::IncrementalComponents;
+///
+/// let mut components = IncrementalComponents::new(4);
+///
+/// assert!(components.add_edge(1, 2));
+/// assert!(components.add_edge(3, 4));
+/// assert!(!components.add_edge(2, 1));
+/// assert!(!components.connected(1, 4));
+/// assert_eq!(components.count(), 2);
+///
+/// let index = components.add_node();
+/// components.add_edge(index, 4);
+///
+/// assert_eq!(components.component_size(3), 3);
+/// assert_eq!(components.components().members(1), Some(&[3, 4, 5][..]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct IncrementalComponents {
+    // parent of node with index `i` at position `i`, position 0 is unused
+    parents: Vec<u32>,
+    // sizes of components, valid only at positions of the roots
+    sizes:   Vec<u32>,
+    count:   u32,
+}
+
+impl IncrementalComponents {
+    /// Creates components of graph with `nodes_count` nodes and without edges
+    ///
+    /// # Arguments
+    ///
+    /// * `nodes_count` - number of nodes
+    pub fn new(nodes_count: u32) -> IncrementalComponents {
+        IncrementalComponents {
+            parents: (0..=nodes_count).collect(),
+            sizes:   vec![1; nodes_count as usize + 1],
+            count:   nodes_count,
+        }
+    }
+
+    /// Returns number of nodes
+    pub fn nodes_count(&self) -> u32 {
+        self.parents.len() as u32 - 1
+    }
+
+    /// Returns number of components
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Adds node in its own component and returns its index
+    pub fn add_node(&mut self) -> u32 {
+        let index = self.parents.len() as u32;
+        self.parents.push(index);
+        self.sizes.push(1);
+        self.count += 1;
+
+        index
+    }
+
+    /// Joins components of the nodes, returns `false` if they were already connected
+    ///
+    /// # Arguments
+    ///
+    /// * `from_index` - index of one end of the edge
+    /// * `to_index` - index of another end of the edge
+    pub fn add_edge(&mut self, from_index: u32, to_index: u32) -> bool {
+        self.assert_node(from_index);
+        self.assert_node(to_index);
+
+        let from_root = self.compressed_root(from_index);
+        let to_root = self.compressed_root(to_index);
+        if from_root == to_root {
+            return false;
+        }
+
+        // smaller component is attached to the bigger one, so paths to the roots stay short
+        let (root, child) = if self.sizes[from_root as usize] < self.sizes[to_root as usize] {
+            (to_root, from_root)
+        } else {
+            (from_root, to_root)
+        };
+        self.parents[child as usize] = root;
+        self.sizes[root as usize] += self.sizes[child as usize];
+        self.count -= 1;
+
+        true
+    }
+
+    /// Checks if nodes are in the same component
+    ///
+    /// # Arguments
+    ///
+    /// * `first_index` - index of the first node
+    /// * `second_index` - index of the second node
+    pub fn connected(&self, first_index: u32, second_index: u32) -> bool {
+        self.assert_node(first_index);
+        self.assert_node(second_index);
+
+        self.root(first_index) == self.root(second_index)
+    }
+
+    /// Returns number of nodes in the component containing the node
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - index of the node
+    pub fn component_size(&self, index: u32) -> u32 {
+        self.assert_node(index);
+
+        self.sizes[self.root(index) as usize]
+    }
+
+    /// Labels every node with id of its component
+    pub fn components(&self) -> Components {
+        let mut root_ids = vec![None; self.parents.len()];
+        let mut members: Vec<Vec<u32>> = vec![];
+
+        let ids = (1..self.parents.len() as u32)
+            .map(|index| {
+                let root = self.root(index) as usize;
+                let id = *root_ids[root].get_or_insert_with(|| {
+                    members.push(vec![]);
+                    members.len() as u32 - 1
+                });
+                members[id as usize].push(index);
+
+                id
+            })
+            .collect();
+
+        Components { ids, members }
+    }
+
+    // position 0 of `parents` is unused, but it's a root, so it must be rejected explicitly
+    fn assert_node(&self, index: u32) {
+        assert!(
+            (1..=self.nodes_count()).contains(&index),
+            "there is no node with index {} (nodes_count is {})",
+            index,
+            self.nodes_count()
+        );
+    }
+
+    fn root(&self, mut index: u32) -> u32 {
+        while self.parents[index as usize] != index {
+            index = self.parents[index as usize];
+        }
+        index
+    }
+
+    // every visited node is attached to its grandparent, which halves the path to the root
+    fn compressed_root(&mut self, mut index: u32) -> u32 {
+        while self.parents[index as usize] != index {
+            self.parents[index as usize] = self.parents[self.parents[index as usize] as usize];
+            index = self.parents[index as usize];
+        }
+        index
+    }
+}
+
+impl<W: Weight> Extend<Edge<W>> for IncrementalComponents {
+    fn extend<T: IntoIterator<Item = Edge<W>>>(&mut self, edges: T) {
+        for edge in edges {
+            self.add_edge(edge.from_index, edge.to_index);
+        }
+    }
+}
+
+impl<W: Weight> From<&Graph<W>> for IncrementalComponents {
+    fn from(graph: &Graph<W>) -> Self {
+        let mut components = IncrementalComponents::new(graph.nodes_count);
+        components.extend(graph.edges.iter().copied());
+        components
+    }
+}
+
+impl<W: Weight> Graph<W> {
+    /// Labels every node with id of its connected component (weakly connected, if graph is directed)
+    ///
+    /// Graph doesn't have to be connected, but [`GraphBuilder`](crate::GraphBuilder) rejects disconnected graphs
+    /// by default - see [`ValidationPolicy::permissive`](crate::ValidationPolicy::permissive).
+    /// To keep components up to date while adding edges, use [`IncrementalComponents`].
+    pub fn connected_components(&self) -> Components {
+        IncrementalComponents::from(self).components()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{build_graph_with_options, ReadOptions, ValidationPolicy};
+    use test_case::test_case;
+
+    fn graph(input: &str) -> Graph {
+        build_graph_with_options(input, ReadOptions::new().with_policy(ValidationPolicy::permissive())).unwrap()
+    }
+
+    #[test_case("0 0" => Vec::<Vec<u32>>::new(); "empty graph")]
+    #[test_case("3 0" => vec![vec![1], vec![2], vec![3]]; "no edges")]
+    #[test_case("4 3\n1 2 10\n2 3 10\n3 4 10" => vec![vec![1, 2, 3, 4]]; "connected graph")]
+    #[test_case("5 3\n5 1 10\n2 4 10\n3 3 10" => vec![vec![1, 5], vec![2, 4], vec![3]]; "disconnected graph")]
+    #[test_case("4 2 directed\n2 1 10\n3 4 10" => vec![vec![1, 2], vec![3, 4]]; "weakly connected")]
+    fn members(input: &str) -> Vec<Vec<u32>> {
+        graph(input)
+            .connected_components()
+            .iter()
+            .map(<[u32]>::to_vec)
+            .collect()
+    }
+
+    #[test]
+    fn component_ids() {
+        let components = graph("5 3\n5 1 10\n2 4 10\n3 3 10").connected_components();
+
+        assert_eq!(components.component_ids(), &[0, 1, 2, 1, 0]);
+        assert_eq!(components.component_of(0), None);
+        assert_eq!(components.component_of(6), None);
+        assert_eq!(components.members(3), None);
+    }
+
+    #[test]
+    fn incremental_components_match_graph_components() {
+        let graph = graph("8 6\n1 2 10\n3 4 10\n2 5 10\n6 7 10\n4 6 10\n5 1 10");
+        let mut components = IncrementalComponents::new(8);
+
+        let joined = graph
+            .edges
+            .iter()
+            .map(|edge| components.add_edge(edge.from_index, edge.to_index))
+            .collect::<Vec<_>>();
+
+        assert_eq!(joined, vec![true, true, true, true, true, false]);
+        assert_eq!(components.count(), 3);
+        assert_eq!(components.component_size(7), 4);
+        assert!(components.connected(3, 7));
+        assert!(!components.connected(1, 8));
+        assert_eq!(components.components(), graph.connected_components());
+    }
+
+    #[test_case(0, 1 => panics "there is no node with index 0"; "zero index")]
+    #[test_case(1, 3 => panics "there is no node with index 3"; "index greater than nodes count")]
+    fn add_edge_to_unknown_node(from_index: u32, to_index: u32) {
+        let mut components = IncrementalComponents::new(2);
+
+        components.add_edge(from_index, to_index);
+    }
+}
@@ -0,0 +1,464 @@
+use crate::reader::{add_line, infer_counts, strip_comment, ParsedLine, COMMENT_START};
+use crate::structures::GraphHeader;
+use crate::{
+    AddingEdgeError,
+    AttributeError,
+    BuildGraphError,
+    BuildGraphResult as Result,
+    DataSource,
+    Graph,
+    GraphBuilder,
+    GraphParametersParsingError,
+    ParsingEdgeError,
+    ReadOptions,
+    Weight,
+};
+use std::convert::TryFrom;
+use std::fmt::{self, Display, Formatter};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::ops::Range;
+
+/// Place of an error in the graph description
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    /// Number of the line in the input (starting from 1, blank and comment lines are counted)
+    pub line_no: usize,
+
+    /// Columns of the offending token (starting from 1, end is exclusive)
+    ///
+    /// If something is missing in the line, the span is one column right after the last token.
+    pub columns: Range<usize>,
+
+    /// Offending token (empty if something is missing in the line)
+    pub token: String,
+
+    /// The whole line containing the error
+    pub line: String,
+}
+
+/// Error found by [`build_graph_with_diagnostics`] together with its place in the input
+#[derive(Debug)]
+pub struct Diagnostic {
+    /// Found error
+    pub error: BuildGraphError,
+
+    /// Place of the error, None if the error isn't related to any line (e.g. graph isn't connected)
+    pub location: Option<Location>,
+}
+
+/// All errors found by [`build_graph_with_diagnostics`], in order of lines
+///
+/// Displayed compiler-style, every error with its source line and a caret under the offending token.
+#[derive(Debug)]
+pub struct Diagnostics(pub Vec<Diagnostic>);
+
+/// Builds a graph from native format, reporting all invalid lines instead of stopping at the first one
+///
+/// Every edge line is checked, including the lines after an invalid one. Errors of the whole graph
+/// ([`BuildGraphError::TooFewEdges`] and [`BuildGraphError::GraphNotConnected`]) are reported only
+/// if all lines are valid, because they are usually caused by the invalid lines. Invalid first line
+/// (graph parameters) stops reading, because edges can't be checked without it, unless the header
+/// is optional (see [`ReadOptions::optional_header`]). Counts to infer are inferred from the valid lines.
+///
+/// Like in [`BuildGraphError::ErrorInGraphDescriptionFile`], line numbers are numbers of lines in the input.
+/// Input must be in native format, so `format` of the options is ignored.
+///
+/// # Example
+/// ```
+/// use graph::{build_graph_with_diagnostics, Graph, ReadOptions};
+///
+/// let diagnostics = build_graph_with_diagnostics::<i32, _>("3 3
+/// 1 2 x
+/// 2 3 100
+/// 2 7 100", ReadOptions::new())
+///     .unwrap_err();
+///
+/// assert_eq!(diagnostics.0.len(), 2);
+/// assert_eq!(diagnostics.to_string(), "\
+/// error: error parsing edge - weight must be a number, but it is: `x`
+///  --> line 2, column 5
+///   |
+/// 2 | 1 2 x
+///   |     ^
+///
+/// error: error adding edge - `2 7 100` to_index field value is greater than nodes count `3` in graph !
+///  --> line 4, column 3
+///   |
+/// 4 | 2 7 100
+///   |   ^
+///
+/// found 2 errors
+/// ");
+/// ```
+///
+/// # Arguments
+///
+/// * `data_source` - a reference to String, bytes, Path or PathBuf, or a [`DataSource`]
+/// * `options` - validation policy of the graph, index of the first node and if the header is optional
+pub fn build_graph_with_diagnostics<'r, W, DS>(data_source: DS, options: ReadOptions) -> Result<Graph<W>, Diagnostics>
+where
+    W: Weight,
+    DS: Into<DataSource<'r>>,
+{
+    // lines are numbered from 1, blank and comment lines are counted
+    let mut lines = text_lines(data_source.into())
+        .map_err(Diagnostics::unlocated)?
+        .enumerate()
+        .map(|(position, line)| line.map(|line| (position + 1, line)));
+
+    // first line that isn't blank or a comment contains graph parameters, if the header is optional
+    // and the line isn't valid graph parameters, it's the first edge line
+    let mut header = None;
+    let mut first_edge_line = None;
+    for line in &mut lines {
+        let (line_no, line) = line.map_err(Diagnostics::unlocated)?;
+        let stripped_line = strip_comment(&line);
+        if stripped_line.is_empty() {
+            continue;
+        }
+
+        match GraphHeader::try_from(stripped_line) {
+            Ok(graph_header) => header = Some(graph_header),
+            Err(_) if options.optional_header => {
+                header = Some(GraphHeader::missing());
+                first_edge_line = Some((line_no, line));
+            }
+            Err(error) => return Err(Diagnostics(vec![Diagnostic::located(error, line_no, line)])),
+        }
+        break;
+    }
+
+    let header =
+        header.ok_or_else(|| Diagnostics::unlocated(BuildGraphError::from(GraphParametersParsingError::EmptyInput)))?;
+    let lines = first_edge_line.into_iter().map(Ok).chain(lines);
+
+    let labelled = header.parameters.labelled;
+    let policy = header.policy(options.policy);
+    let builder = |graph_parameters| {
+        GraphBuilder::new(graph_parameters)
+            .with_validation_policy(policy)
+            .with_index_base(options.index_base)
+    };
+
+    if !header.nodes_count_inferred && !header.edges_count_inferred {
+        return build_graph_from_located_lines(builder(header.parameters), lines);
+    }
+
+    // invalid lines are reported while adding them, so counts are inferred only from the valid ones
+    let lines = lines.collect::<Result<Vec<_>>>().map_err(Diagnostics::unlocated)?;
+    let valid_lines = lines
+        .iter()
+        .map(|(line_no, line)| (*line_no, strip_comment(line)))
+        .filter(|(_, line)| !line.is_empty() && ParsedLine::<W>::parse(line, labelled).is_ok())
+        .collect::<Vec<_>>();
+    let graph_parameters =
+        infer_counts::<W, _>(header, &valid_lines, options.index_base).map_err(Diagnostics::unlocated)?;
+
+    build_graph_from_located_lines(builder(graph_parameters), lines.into_iter().map(Ok))
+}
+
+// `lines` are pairs (line number, line), every invalid line is reported
+fn build_graph_from_located_lines<W, I>(mut graph_builder: GraphBuilder<W>, lines: I) -> Result<Graph<W>, Diagnostics>
+where
+    W: Weight,
+    I: Iterator<Item = Result<(usize, String)>>,
+{
+    let labelled = graph_builder.is_labelled();
+    let mut diagnostics = vec![];
+
+    for line in lines {
+        let (line_no, line) = line.map_err(Diagnostics::unlocated)?;
+        let stripped_line = strip_comment(&line);
+        if stripped_line.is_empty() {
+            continue;
+        }
+
+        if let Err(error) = add_line(&mut graph_builder, labelled, stripped_line) {
+            diagnostics.push(Diagnostic::located(error, line_no, line));
+        }
+    }
+
+    if !diagnostics.is_empty() {
+        return Err(Diagnostics(diagnostics));
+    }
+
+    graph_builder.build().map_err(Diagnostics::unlocated)
+}
+
+// every line is read as an owned string, so it can be kept in the diagnostic
+fn text_lines<'r>(data_source: DataSource<'r>) -> Result<Box<dyn Iterator<Item = Result<String>> + 'r>> {
+    let read_lines = |reader: Box<dyn BufRead + 'r>| -> Box<dyn Iterator<Item = Result<String>> + 'r> {
+        Box::new(reader.lines().map(|line| line.map_err(BuildGraphError::from)))
+    };
+
+    Ok(match data_source {
+        DataSource::String(s) => Box::new(s.lines().map(|line| Ok(line.to_owned()))),
+        DataSource::Bytes(bytes) => {
+            let input =
+                std::str::from_utf8(bytes).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+            Box::new(input.lines().map(|line| Ok(line.to_owned())))
+        }
+        DataSource::File(filename) => read_lines(Box::new(BufReader::new(File::open(filename)?))),
+        DataSource::Reader(reader) => read_lines(reader),
+    })
+}
+
+impl Diagnostic {
+    fn located(error: BuildGraphError, line_no: usize, line: String) -> Diagnostic {
+        let span = error_span(&error, &line, &token_spans(&line));
+
+        let token = line.get(span.clone()).unwrap_or_default().to_owned();
+
+        // span of missing token might be after the end of the line
+        let column = |byte: usize| line[..byte.min(line.len())].chars().count() + byte.saturating_sub(line.len()) + 1;
+
+        let location = Location {
+            line_no,
+            columns: column(span.start)..column(span.end),
+            token,
+            line,
+        };
+
+        Diagnostic {
+            error,
+            location: Some(location),
+        }
+    }
+}
+
+// byte ranges of the tokens in the line, comment is skipped
+fn token_spans(line: &str) -> Vec<Range<usize>> {
+    let content = &line[..line.find(COMMENT_START).unwrap_or(line.len())];
+
+    let mut spans = vec![];
+    let mut start = None;
+    for (position, c) in content.char_indices().chain(std::iter::once((content.len(), ' '))) {
+        match (start, c.is_whitespace()) {
+            (None, false) => start = Some(position),
+            (Some(token_start), true) => {
+                spans.push(token_start..position);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    spans
+}
+
+// byte range of the token causing the error, one byte after the last token if something is missing
+fn error_span(error: &BuildGraphError, line: &str, tokens: &[Range<usize>]) -> Range<usize> {
+    let end = tokens.last().map_or(0, |token| token.end);
+    let missing = (end + 1)..(end + 2);
+
+    // edge is described by first three tokens
+    let first = tokens.first().map_or(0, |token| token.start);
+    let whole = first..tokens[..tokens.len().min(3)].last().map_or(0, |token| token.end);
+
+    let token = |index: usize| tokens.get(index).cloned().unwrap_or_else(|| missing.clone());
+    let token_with_text = |text: &str, range: Range<usize>| {
+        tokens[range.start.min(tokens.len())..range.end.min(tokens.len())]
+            .iter()
+            .find(|token| &line[(*token).clone()] == text)
+            .cloned()
+            .unwrap_or_else(|| whole.clone())
+    };
+
+    match error {
+        BuildGraphError::GraphParametersParsingError(error) => match error {
+            GraphParametersParsingError::NodesCountValueMustBeInteger(_) => token(0),
+            GraphParametersParsingError::EdgesCountValueIsNotInteger(_) => token(1),
+            GraphParametersParsingError::MissingEdgesCountValue => missing,
+            GraphParametersParsingError::UnknownGraphOption(option) => token_with_text(option, 2..tokens.len()),
+            GraphParametersParsingError::EmptyInput => whole,
+        },
+        BuildGraphError::ParsingEdgeError(error) => match error {
+            ParsingEdgeError::FromIndexValueMustBeInteger(_) => token(0),
+            ParsingEdgeError::ToIndexValueMustBeInteger(_) => token(1),
+            ParsingEdgeError::WeightValueMustBeInteger(_) => token(2),
+            ParsingEdgeError::UnexpectedTrailingToken(_) => token(3),
+            ParsingEdgeError::MissingToIndexField | ParsingEdgeError::MissingWeightField => missing,
+            ParsingEdgeError::EmptyLine => whole,
+        },
+        BuildGraphError::AddingEdgeError(error) => match error {
+            AddingEdgeError::ZeroIndex { .. } => token_with_text("0", 0..2),
+            AddingEdgeError::WrongFromIndex { .. } => token(0),
+            AddingEdgeError::WrongToIndex { .. } => token(1),
+            AddingEdgeError::NegativeWeight { .. } => token(2),
+            AddingEdgeError::TooManyNodeLabels { label, .. } => token_with_text(label, 0..2),
+            _ => whole,
+        },
+        BuildGraphError::AttributeError(error) => match error {
+            // attributes follow the edge or the node in node line
+            AttributeError::InvalidAttribute(attribute) => token_with_text(attribute, 2..tokens.len()),
+            AttributeError::NodeIndexValueMustBeInteger(_) | AttributeError::UnknownNode { .. } => token(1),
+            AttributeError::UnknownEdge { .. } => whole,
+        },
+        _ => whole,
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "error: {}", self.error)?;
+
+        if let Some(location) = &self.location {
+            let gutter = " ".repeat(location.line_no.to_string().len());
+            let caret_count = (location.columns.end - location.columns.start).max(1);
+
+            writeln!(
+                f,
+                "{}--> line {}, column {}",
+                gutter, location.line_no, location.columns.start
+            )?;
+            writeln!(f, "{} |", gutter)?;
+            writeln!(f, "{} | {}", location.line_no, location.line)?;
+            writeln!(
+                f,
+                "{} | {}{}",
+                gutter,
+                " ".repeat(location.columns.start - 1),
+                "^".repeat(caret_count)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for Diagnostics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for diagnostic in &self.0 {
+            writeln!(f, "{}", diagnostic)?;
+        }
+
+        match self.0.len() {
+            1 => writeln!(f, "found 1 error"),
+            count => writeln!(f, "found {} errors", count),
+        }
+    }
+}
+
+impl Diagnostics {
+    // error which isn't related to any line
+    fn unlocated(error: BuildGraphError) -> Diagnostics {
+        Diagnostics(vec![Diagnostic { error, location: None }])
+    }
+}
+
+impl std::error::Error for Diagnostics {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IndexBase;
+    use test_case::test_case;
+
+    fn locations(input: &str) -> Vec<Option<(usize, Range<usize>, String)>> {
+        build_graph_with_diagnostics::<i32, _>(input, ReadOptions::new())
+            .unwrap_err()
+            .0
+            .into_iter()
+            .map(|diagnostic| {
+                diagnostic
+                    .location
+                    .map(|location| (location.line_no, location.columns, location.token))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn all_invalid_lines_are_reported() {
+        let input = "# garden paths
+            3 3
+
+            1 2 x
+            2 3 100 # second path
+            2 y 40
+            3 9 100";
+
+        assert_eq!(locations(input), vec![
+            Some((4, 17..18, "x".to_owned())),
+            Some((6, 15..16, "y".to_owned())),
+            Some((7, 15..16, "9".to_owned())),
+        ]);
+    }
+
+    #[test_case("3 2\n1 2 100\n2 3", 3, 5..6; "missing weight")]
+    #[test_case("3 2\n1 2 100\n2 3 100 70", 3, 9..11; "trailing token")]
+    #[test_case("3 x\n1 2 100", 1, 3..4; "edges count isn't a number")]
+    #[test_case("3 2 labeled\n1 2 100", 1, 5..12; "unknown option")]
+    #[test_case("2 1 labels\ngate pond 100\npond garage 80", 3, 6..12; "too many labels")]
+    #[test_case("3 2\n1 2 100\n3 0 100", 3, 3..4; "zero index")]
+    #[test_case("3 2\n1 2 100 lanes=2 toll\n2 3 100", 2, 17..21; "invalid attribute")]
+    #[test_case("3 2\n1 2 100\n2 3 100\nnode 7 color=red", 4, 6..7; "node line with unknown node")]
+    fn error_location(input: &str, line_no: usize, columns: Range<usize>) {
+        let found = locations(input);
+
+        assert_eq!(found.len(), 1);
+        let (found_line_no, found_columns, _) = found[0].clone().unwrap();
+        assert_eq!((found_line_no, found_columns), (line_no, columns));
+    }
+
+    #[test]
+    fn edges_are_not_checked_after_invalid_parameters() {
+        assert_eq!(locations("3 x\n1 2 y\n2 3 z").len(), 1);
+    }
+
+    #[test]
+    fn zero_based_indices() {
+        let options = ReadOptions::new().with_index_base(IndexBase::Zero);
+
+        let graph: Graph = build_graph_with_diagnostics("2 1\n0 1 10", options).unwrap();
+        assert_eq!(graph.edges[0], "1 2 10".parse().unwrap());
+
+        let diagnostics = build_graph_with_diagnostics::<i32, _>("2 1\n1 2 10", options).unwrap_err();
+        assert_eq!(diagnostics.0[0].location.as_ref().unwrap().token, "2");
+    }
+
+    #[test_case("? ?\n1 2 100\n2 x 80\n2 3 y", ReadOptions::new(); "inferred counts")]
+    #[test_case("1 2 100\n2 x 80\n2 3 y", ReadOptions::new().with_optional_header(true); "missing header")]
+    fn all_invalid_lines_are_reported_with_inferred_counts(input: &str, options: ReadOptions) {
+        let diagnostics = build_graph_with_diagnostics::<i32, _>(input, options).unwrap_err();
+
+        let tokens = diagnostics
+            .0
+            .iter()
+            .map(|diagnostic| diagnostic.location.as_ref().unwrap().token.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(tokens, vec!["x", "y"]);
+    }
+
+    #[test]
+    fn missing_header() {
+        let options = ReadOptions::new().with_optional_header(true);
+
+        let graph: Graph = build_graph_with_diagnostics("# roads\n1 2 100\n\n2 3 80", options).unwrap();
+
+        assert_eq!(graph.nodes_count, 3);
+        assert_eq!(graph.edges.len(), 2);
+    }
+
+    #[test]
+    fn graph_errors_have_no_location() {
+        assert_eq!(locations("3 2\n1 2 100"), vec![None]);
+    }
+
+    #[test]
+    fn missing_field_is_marked_after_the_line() {
+        let diagnostics = build_graph_with_diagnostics::<i32, _>("2 1\n1 2", ReadOptions::new()).unwrap_err();
+
+        assert_eq!(
+            diagnostics.to_string(),
+            "error: error parsing edge - missing `weight` field
+ --> line 2, column 5
+  |
+2 | 1 2
+  |     ^
+
+found 1 error
+"
+        );
+    }
+}
@@ -0,0 +1,227 @@
+use crate::{
+    BuildGraphError,
+    BuildGraphResult as Result,
+    DimacsParsingError,
+    Edge,
+    EdgeDescription,
+    Graph,
+    GraphBuilder,
+    GraphKind,
+    GraphParameters,
+    GraphParametersParsingError,
+    ReadOptions,
+    Weight,
+};
+use std::convert::TryFrom;
+use std::io::{self, Write};
+
+/// Descriptor of the comment line
+const COMMENT_LINE: &str = "c";
+
+/// Descriptor of the problem line (`p sp nodes_count arcs_count`)
+const PROBLEM_LINE: &str = "p";
+
+/// Descriptor of the arc line (`a from_index to_index weight`)
+const ARC_LINE: &str = "a";
+
+/// The only supported problem type - shortest path
+const SHORTEST_PATH_PROBLEM: &str = "sp";
+
+/// Checks if the line starts with one of DIMACS line descriptors
+pub(crate) fn is_dimacs_line(line: &str) -> bool {
+    matches!(
+        line.split_whitespace().next(),
+        Some(COMMENT_LINE | PROBLEM_LINE | ARC_LINE)
+    )
+}
+
+/// Builds a directed graph from lines in the 9th DIMACS Challenge shortest path (`.gr`) format
+///
+/// Input contains comment lines (`c ...`), exactly one problem line (`p sp nodes_count arcs_count`)
+/// and then `arcs_count` arc lines (`a from_index to_index weight`). Blank lines are skipped.
+///
+/// Number of invalid line in returned [`BuildGraphError::ErrorInGraphDescriptionFile`] is the number of line in input
+/// (starting from 1).
+pub(crate) fn build_graph_from_dimacs_lines<W, L, I>(lines: I, options: ReadOptions) -> Result<Graph<W>>
+where
+    W: Weight,
+    L: AsRef<str>,
+    I: Iterator<Item = Result<L>>,
+{
+    let mut graph_builder: Option<GraphBuilder<W>> = None;
+
+    for (line_no, line) in lines.enumerate() {
+        let line = line?;
+        let line = line.as_ref().trim();
+
+        let mut parse_line = || -> Result<()> {
+            let (descriptor, rest) = line.split_at(line.find(char::is_whitespace).unwrap_or(line.len()));
+
+            match descriptor {
+                "" | COMMENT_LINE => Ok(()),
+                PROBLEM_LINE if graph_builder.is_some() => {
+                    Err(BuildGraphError::from(DimacsParsingError::UnexpectedProblemLine))
+                }
+                PROBLEM_LINE => {
+                    graph_builder = Some(
+                        GraphBuilder::new(parse_problem_line(line, rest)?)
+                            .with_validation_policy(options.policy)
+                            .with_index_base(options.index_base),
+                    );
+                    Ok(())
+                }
+                ARC_LINE => {
+                    let graph_builder = graph_builder
+                        .as_mut()
+                        .ok_or_else(|| BuildGraphError::from(DimacsParsingError::MissingProblemLine))?;
+                    graph_builder.add_edge(Edge::try_from(EdgeDescription::try_from(rest)?)?)
+                }
+                _ => Err(BuildGraphError::from(DimacsParsingError::UnknownLineDescriptor(
+                    descriptor.to_owned(),
+                ))),
+            }
+        };
+
+        parse_line().map_err(|error| BuildGraphError::ErrorInGraphDescriptionFile {
+            line_no: line_no + 1,
+            error:   Box::new(error),
+        })?;
+    }
+
+    graph_builder
+        .ok_or_else(|| BuildGraphError::from(DimacsParsingError::MissingProblemLine))?
+        .build()
+}
+
+// `rest` is the part of the problem line following `p` descriptor
+fn parse_problem_line(line: &str, rest: &str) -> Result<GraphParameters> {
+    let tokens: Vec<&str> = rest.split_whitespace().collect();
+
+    let (problem, nodes_count, arcs_count) = match tokens.as_slice() {
+        [problem, nodes_count, arcs_count] => (*problem, *nodes_count, *arcs_count),
+        _ => {
+            return Err(BuildGraphError::from(DimacsParsingError::InvalidProblemLine(
+                line.to_owned(),
+            )))
+        }
+    };
+
+    if problem != SHORTEST_PATH_PROBLEM {
+        return Err(BuildGraphError::from(DimacsParsingError::UnsupportedProblem(
+            problem.to_owned(),
+        )));
+    }
+
+    let nodes_count = nodes_count.parse::<u32>().map_err(|_| {
+        BuildGraphError::from(GraphParametersParsingError::NodesCountValueMustBeInteger(
+            nodes_count.to_owned(),
+        ))
+    })?;
+
+    let arcs_count = arcs_count.parse::<usize>().map_err(|_| {
+        BuildGraphError::from(GraphParametersParsingError::EdgesCountValueIsNotInteger(
+            arcs_count.to_owned(),
+        ))
+    })?;
+
+    Ok(GraphParameters::new(nodes_count, arcs_count).with_kind(GraphKind::Directed))
+}
+
+/// Writes graph in the 9th DIMACS Challenge shortest path (`.gr`) format
+///
+/// Every edge of an undirected graph is written as two arcs (one in each direction).
+/// Node labels aren't written, nodes are identified by their indices.
+///
+/// # Example
+/// ```
+/// use graph::{write_dimacs, Graph};
+///
+/// let graph: Graph = "3 3 directed
+///     1 2 100
+///     2 3 150
+///     3 1 80"
+///     .parse()
+///     .unwrap();
+///
+/// let mut output = vec![];
+/// write_dimacs(&graph, &mut output).unwrap();
+///
+/// assert_eq!(String::from_utf8(output).unwrap(), "p sp 3 3\na 1 2 100\na 2 3 150\na 3 1 80\n");
+/// ```
+///
+/// # Arguments
+///
+/// * `graph` - graph that will be written
+/// * `writer` - destination of the output
+pub fn write_dimacs<W: Weight, Wr: Write>(graph: &Graph<W>, mut writer: Wr) -> io::Result<()> {
+    let arcs_count = match graph.kind {
+        GraphKind::Directed => graph.edges.len(),
+        GraphKind::Undirected => graph.edges.len() * 2,
+    };
+
+    writeln!(
+        writer,
+        "{} {} {} {}",
+        PROBLEM_LINE, SHORTEST_PATH_PROBLEM, graph.nodes_count, arcs_count
+    )?;
+
+    for edge in &graph.edges {
+        writeln!(writer, "{} {}", ARC_LINE, edge)?;
+
+        if graph.kind == GraphKind::Undirected {
+            writeln!(
+                writer,
+                "{} {} {} {}",
+                ARC_LINE, edge.to_index, edge.from_index, edge.weight
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{build_graph_in_format, GraphFormat};
+
+    #[test]
+    fn write_undirected() -> Result<()> {
+        let graph: Graph = "3 2
+            1 2 100
+            3 1 40"
+            .parse()?;
+
+        let mut output = vec![];
+        write_dimacs(&graph, &mut output)?;
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "p sp 3 4\na 1 2 100\na 2 1 100\na 3 1 40\na 1 3 40\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn write_and_read() -> Result<()> {
+        let graph: Graph = "4 4 directed
+            1 2 100
+            2 3 70
+            3 4 20
+            4 1 35"
+            .parse()?;
+
+        let mut output = vec![];
+        write_dimacs(&graph, &mut output)?;
+
+        let output = String::from_utf8(output).unwrap();
+        let read_graph: Graph = build_graph_in_format(output.as_str(), GraphFormat::Dimacs)?;
+
+        assert_eq!(read_graph.nodes_count, graph.nodes_count);
+        assert_eq!(read_graph.kind, GraphKind::Directed);
+        assert_eq!(read_graph.edges, graph.edges);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,286 @@
+use crate::structures::nodes_pair;
+use crate::{AttributeValue, Attributes, Edge, Graph, GraphKind, Weight};
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
+
+/// Color of nodes and edges on the highlighted path
+const PATH_COLOR: &str = "red";
+
+/// Writes [`Graph`] in Graphviz DOT language, optionally with an algorithm result laid over it
+///
+/// Edges chosen with [`DotWriter::with_bold_edges`] (e.g. minimum spanning tree) are drawn bold,
+/// nodes and edges of the path given with [`DotWriter::with_path`] (e.g. shortest path) are colored.
+/// Attributes of nodes and edges (see [`crate::Attributes`]) are written as DOT attributes, so e.g. `color=blue`
+/// attribute is used by Graphviz.
+///
+/// # Example
+/// ```
+/// use graph::{DotWriter, Graph};
+///
+/// let graph: Graph = "3 3
+///     1 2 100
+///     2 3 150
+///     1 3 300"
+///     .parse()
+///     .unwrap();
+///
+/// let mut output = vec![];
+/// DotWriter::new(&graph).with_bold_edges(vec![0, 1]).write(&mut output).unwrap();
+///
+/// assert_eq!(String::from_utf8(output).unwrap(), "graph {
+///     1;
+///     2;
+///     3;
+///     1 -- 2 [label=\"100\", style=bold];
+///     2 -- 3 [label=\"150\", style=bold];
+///     1 -- 3 [label=\"300\"];
+/// }
+/// ");
+/// ```
+pub struct DotWriter<'g, W> {
+    /// Graph that will be written
+    graph: &'g Graph<W>,
+
+    /// Indices of edges (in [`Graph::edges`]) drawn bold
+    bold_edges: HashSet<usize>,
+
+    /// Indices of consecutive nodes of the colored path
+    path: Vec<u32>,
+}
+
+impl<'g, W: Weight> DotWriter<'g, W> {
+    /// Creates writer of the graph without any highlights
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - graph that will be written
+    pub fn new(graph: &'g Graph<W>) -> DotWriter<'g, W> {
+        DotWriter {
+            graph,
+            bold_edges: HashSet::new(),
+            path: vec![],
+        }
+    }
+
+    /// Makes given edges drawn bold
+    ///
+    /// # Arguments
+    ///
+    /// * `edges` - indices of edges in [`Graph::edges`]
+    pub fn with_bold_edges<I: IntoIterator<Item = usize>>(self, edges: I) -> DotWriter<'g, W> {
+        DotWriter {
+            bold_edges: edges.into_iter().collect(),
+            ..self
+        }
+    }
+
+    /// Makes given path colored
+    ///
+    /// If consecutive nodes of the path are connected by more than one edge, the lightest one is colored.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - indices of consecutive nodes of the path
+    pub fn with_path(self, path: &[u32]) -> DotWriter<'g, W> {
+        DotWriter {
+            path: path.to_vec(),
+            ..self
+        }
+    }
+
+    /// Writes the graph to given writer
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - destination of the output
+    pub fn write<Wr: Write>(&self, mut writer: Wr) -> io::Result<()> {
+        let (graph_type, edge_operator) = match self.graph.kind {
+            GraphKind::Directed => ("digraph", "->"),
+            GraphKind::Undirected => ("graph", "--"),
+        };
+
+        writeln!(writer, "{} {{", graph_type)?;
+
+        let path_nodes: HashSet<u32> = self.path.iter().copied().collect();
+        for index in 1..=self.graph.nodes_count {
+            let mut attributes = vec![];
+
+            if let Some(label) = self.graph.labels().and_then(|labels| labels.label(index)) {
+                attributes.push(format!("label={}", quoted(label)));
+            }
+            if path_nodes.contains(&index) {
+                attributes.push(format!("color={}", PATH_COLOR));
+            }
+            if let Some(node_attributes) = self.graph.node_attributes_at(index) {
+                attributes.extend(dot_attributes(node_attributes));
+            }
+
+            writeln!(writer, "    {}{};", index, attributes_list(&attributes))?;
+        }
+
+        let path_edges = self.path_edges();
+        for (position, edge) in self.graph.edges.iter().enumerate() {
+            let mut attributes = vec![format!("label={}", quoted(&edge.weight.to_string()))];
+
+            if self.bold_edges.contains(&position) {
+                attributes.push("style=bold".to_owned());
+            }
+            if path_edges.contains(&position) {
+                attributes.push(format!("color={}", PATH_COLOR));
+            }
+            if let Some(edge_attributes) = self.graph.edge_attributes_at(position) {
+                attributes.extend(dot_attributes(edge_attributes));
+            }
+
+            writeln!(
+                writer,
+                "    {} {} {}{};",
+                edge.from_index,
+                edge_operator,
+                edge.to_index,
+                attributes_list(&attributes)
+            )?;
+        }
+
+        writeln!(writer, "}}")
+    }
+
+    // finds positions of the lightest edges connecting consecutive nodes of the path
+    fn path_edges(&self) -> HashSet<usize> {
+        if self.path.len() < 2 {
+            return HashSet::new();
+        }
+
+        // every pair of nodes is mapped to the lightest edge connecting them (the first one, if there are
+        // more such edges), so every step of the path is found without scanning all edges
+        let mut lightest_edges = HashMap::new();
+        for (position, edge) in self.graph.edges.iter().enumerate() {
+            lightest_edges
+                .entry(nodes_pair(edge, self.graph.kind))
+                .and_modify(|lightest: &mut usize| {
+                    if edge.weight < self.graph.edges[*lightest].weight {
+                        *lightest = position;
+                    }
+                })
+                .or_insert(position);
+        }
+
+        self.path
+            .windows(2)
+            .filter_map(|step| {
+                let step = Edge::new(step[0], step[1], W::zero());
+                lightest_edges.get(&nodes_pair(&step, self.graph.kind)).copied()
+            })
+            .collect()
+    }
+}
+
+// attributes are written as `[name=value, ...]`, nothing is written if there are no attributes
+fn attributes_list(attributes: &[String]) -> String {
+    if attributes.is_empty() {
+        String::new()
+    } else {
+        format!(" [{}]", attributes.join(", "))
+    }
+}
+
+// attributes of nodes and edges with names that aren't DOT identifiers written as strings
+fn dot_attributes(attributes: &Attributes) -> impl Iterator<Item = String> + '_ {
+    attributes.iter().map(|(name, value)| {
+        let is_identifier = !name.is_empty()
+            && !name.starts_with(|c: char| c.is_ascii_digit())
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        let name = if is_identifier { name.to_owned() } else { quoted(name) };
+
+        // text is written as it is, without escapes of graph description
+        let value = match value {
+            AttributeValue::Text(text) => text.clone(),
+            value => value.to_string(),
+        };
+        format!("{}={}", name, quoted(&value))
+    })
+}
+
+// DOT string literal
+fn quoted(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directed_with_path() -> crate::BuildGraphResult<()> {
+        let graph: Graph = "3 4 directed labels
+            gate pond 100
+            pond fountain 50
+            fountain gate 70
+            gate pond 80"
+            .parse()?;
+
+        let mut output = vec![];
+        DotWriter::new(&graph).with_path(&[1, 2, 3]).write(&mut output)?;
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "digraph {
+    1 [label=\"gate\", color=red];
+    2 [label=\"pond\", color=red];
+    3 [label=\"fountain\", color=red];
+    1 -> 2 [label=\"100\"];
+    2 -> 3 [label=\"50\", color=red];
+    3 -> 1 [label=\"70\"];
+    1 -> 2 [label=\"80\", color=red];
+}
+"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn undirected_path_against_edges_direction() -> crate::BuildGraphResult<()> {
+        let graph: Graph = "3 3\n1 2 100\n2 3 50\n3 2 40".parse()?;
+
+        let mut output = vec![];
+        DotWriter::new(&graph).with_path(&[3, 2, 1]).write(&mut output)?;
+
+        let colored_edges = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .filter(|line| line.contains("--") && line.contains("color=red"))
+            .map(str::trim)
+            .map(str::to_owned)
+            .collect::<Vec<_>>();
+        assert_eq!(colored_edges, vec![
+            "1 -- 2 [label=\"100\", color=red];",
+            "3 -- 2 [label=\"40\", color=red];",
+        ]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn attributes() -> crate::BuildGraphResult<()> {
+        let graph: Graph = "2 1
+            1 2 100 color=blue max%20speed=50
+            node 2 label=Main%20Square"
+            .parse()?;
+
+        let mut output = vec![];
+        DotWriter::new(&graph).write(&mut output)?;
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "graph {
+    1;
+    2 [label=\"Main Square\"];
+    1 -- 2 [label=\"100\", color=\"blue\", \"max speed\"=\"50\"];
+}
+"
+        );
+
+        Ok(())
+    }
+}
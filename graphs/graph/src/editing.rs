@@ -0,0 +1,470 @@
+use crate::{Edge, Graph, GraphKind, Weight};
+use parse_display::Display;
+use thiserror::Error;
+
+/// Type returned by methods editing the graph
+pub type EditGraphResult<T, E = EditGraphError> = std::result::Result<T, E>;
+
+/// Errors returned by methods editing the graph
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum EditGraphError {
+    /// Node was removed from the graph or never belonged to it
+    #[error("there is no node {0} in the graph")]
+    UnknownNode(NodeId),
+
+    /// Edge was removed from the graph or never belonged to it
+    #[error("there is no edge {0} in the graph")]
+    UnknownEdge(EdgeId),
+}
+
+/// Handle of the node in [`Graph`], which stays valid when other nodes or edges are removed
+///
+/// Node index (used in edges and algorithms) is decreased, when a node with smaller index is removed,
+/// but its `NodeId` doesn't change. Until the first node is removed, `NodeId` of every node is its index.
+#[derive(Copy, Clone, Debug, Display, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[display("#{0}")]
+pub struct NodeId(pub(crate) u32);
+
+/// Handle of the edge in [`Graph`], which stays valid when other nodes or edges are removed
+///
+/// Until the first edge is removed, `EdgeId` of every edge is its position in [`Graph::edges`].
+#[derive(Copy, Clone, Debug, Display, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[display("#{0}")]
+pub struct EdgeId(pub(crate) usize);
+
+/// Ids of items kept in a vector, which don't change when other items are removed
+///
+/// Until the first removal id of every item is its position, so nothing has to be stored.
+/// Items added to the vector without [`IdMap::push`] get next free ids.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct IdMap {
+    /// Ids in order of positions (always ascending), None if every id is a position
+    ids: Option<Vec<usize>>,
+
+    /// First id that wasn't given to any item, ids of removed items are never reused
+    next_id: usize,
+}
+
+impl IdMap {
+    fn id(&self, position: usize) -> usize {
+        match &self.ids {
+            None => position,
+            Some(ids) => ids
+                .get(position)
+                .copied()
+                .unwrap_or(self.next_id + position - ids.len()),
+        }
+    }
+
+    fn position(&self, id: usize, len: usize) -> Option<usize> {
+        let position = match &self.ids {
+            None => id,
+            Some(ids) => match ids.binary_search(&id) {
+                Ok(position) => position,
+                Err(_) => id.checked_sub(self.next_id)? + ids.len(),
+            },
+        };
+
+        Some(position).filter(|position| *position < len)
+    }
+
+    // gives id to the item added at the end of the vector with `len` items
+    fn push(&mut self, len: usize) -> usize {
+        self.sync(len);
+        match &mut self.ids {
+            None => len,
+            Some(ids) => {
+                ids.push(self.next_id);
+                self.next_id += 1;
+                self.next_id - 1
+            }
+        }
+    }
+
+    fn remove(&mut self, position: usize, len: usize) {
+        self.store(len);
+        if let Some(ids) = &mut self.ids {
+            ids.remove(position);
+        }
+    }
+
+    // removes ids of items, which aren't marked in `kept` (it contains a value for every item)
+    pub(crate) fn retain(&mut self, kept: &[bool]) {
+        self.store(kept.len());
+        if let Some(ids) = &mut self.ids {
+            let mut kept = kept.iter();
+            ids.retain(|_| kept.next().copied().unwrap_or(true));
+        }
+    }
+
+    // ids have to be stored before the first removal, because they stop being positions
+    fn store(&mut self, len: usize) {
+        if self.ids.is_none() {
+            self.ids = Some((0..len).collect());
+            self.next_id = len;
+        }
+
+        self.sync(len);
+    }
+
+    // stores ids of items added directly to the vector
+    fn sync(&mut self, len: usize) {
+        if let Some(ids) = &mut self.ids {
+            while ids.len() < len {
+                ids.push(self.next_id);
+                self.next_id += 1;
+            }
+        }
+    }
+}
+
+/// Methods changing the graph after it's built
+///
+/// Nodes and edges are referred to by [`NodeId`] and [`EdgeId`] handles. Every change resets
+/// the adjacency structure of the graph, so it's rebuilt by the next algorithm (or [`Graph::neighbours`]) using it.
+/// Checks done by [`crate::GraphBuilder`] (e.g. connectivity) aren't repeated.
+///
+/// # Example
+/// ```
+/// use graph::Graph;
+///
+/// let mut graph: Graph = "3 2
+///     1 2 100
+///     2 3 80"
+///     .parse()
+///     .unwrap();
+///
+/// let gate = graph.node_id(1).unwrap();
+/// let fountain = graph.add_node();
+///
+/// let path = graph.add_edge(gate, fountain, 40).unwrap();
+/// assert_eq!(graph.neighbours(1).collect::<Vec<_>>(), vec![(2, 100), (4, 40)]);
+///
+/// graph.remove_node(graph.node_id(2).unwrap()).unwrap();
+///
+/// assert_eq!(graph.nodes_count(), 3);
+/// assert_eq!(graph.edges(), vec!["1 3 40".parse().unwrap()]);
+/// assert_eq!(graph.edge(path), Some(&graph.edges()[0]));
+/// assert_eq!(graph.node_index(fountain), Some(3));
+/// ```
+impl<W: Weight> Graph<W> {
+    /// Returns handle of the node with given index (or None if there is no such node)
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - index of the node
+    pub fn node_id(&self, index: u32) -> Option<NodeId> {
+        let position = index.checked_sub(1).filter(|_| index <= self.nodes_count)?;
+        Some(NodeId(self.node_ids.id(position as usize) as u32 + 1))
+    }
+
+    /// Returns current index of the node (or None if it was removed)
+    ///
+    /// # Arguments
+    ///
+    /// * `node` - handle of the node
+    pub fn node_index(&self, node: NodeId) -> Option<u32> {
+        let position = self
+            .node_ids
+            .position(node.0.checked_sub(1)? as usize, self.nodes_count as usize)?;
+        Some(position as u32 + 1)
+    }
+
+    /// Returns handle of the edge at given position in [`Graph::edges`] (or None if there is no such edge)
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - position of the edge
+    pub fn edge_id(&self, position: usize) -> Option<EdgeId> {
+        Some(EdgeId(self.edge_ids.id(position))).filter(|_| position < self.edges.len())
+    }
+
+    /// Returns edge with given handle (or None if it was removed)
+    ///
+    /// # Arguments
+    ///
+    /// * `edge` - handle of the edge
+    pub fn edge(&self, edge: EdgeId) -> Option<&Edge<W>> {
+        self.edge_position(edge).map(|position| &self.edges[position])
+    }
+
+    /// Returns handle of the first edge connecting given nodes (or None if there is no such edge)
+    ///
+    /// Edge of undirected graph connects nodes in both directions.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - node, where edge starts
+    /// * `to` - node, where edge ends
+    pub fn find_edge(&self, from: NodeId, to: NodeId) -> Option<EdgeId> {
+        let from_index = self.node_index(from)?;
+        let to_index = self.node_index(to)?;
+
+        let connects = |edge: &Edge<W>| match self.kind {
+            GraphKind::Directed => edge.from_index == from_index && edge.to_index == to_index,
+            GraphKind::Undirected => {
+                (edge.from_index, edge.to_index) == (from_index, to_index)
+                    || (edge.from_index, edge.to_index) == (to_index, from_index)
+            }
+        };
+
+        let position = self.edges.iter().position(connects)?;
+        self.edge_id(position)
+    }
+
+    /// Checks if there is an edge connecting given nodes (see [`Graph::find_edge`])
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - node, where edge starts
+    /// * `to` - node, where edge ends
+    pub fn contains_edge(&self, from: NodeId, to: NodeId) -> bool {
+        self.find_edge(from, to).is_some()
+    }
+
+    /// Adds node without edges (and without label) and returns its handle
+    pub fn add_node(&mut self) -> NodeId {
+        let id = self.node_ids.push(self.nodes_count as usize);
+        self.nodes_count += 1;
+        self.adjacency.invalidate();
+
+        NodeId(id as u32 + 1)
+    }
+
+    /// Removes node together with all edges connecting it (and their attributes), nodes with greater indices
+    /// get indices smaller by 1
+    ///
+    /// Returns removed edges or [`EditGraphError::UnknownNode`] if there is no such node.
+    ///
+    /// # Arguments
+    ///
+    /// * `node` - handle of the node
+    pub fn remove_node(&mut self, node: NodeId) -> EditGraphResult<Vec<Edge<W>>> {
+        let index = self.node_index(node).ok_or(EditGraphError::UnknownNode(node))?;
+
+        let kept = self
+            .edges
+            .iter()
+            .map(|edge| edge.from_index != index && edge.to_index != index)
+            .collect::<Vec<_>>();
+        for (position, _) in kept.iter().enumerate().filter(|(_, kept)| !**kept) {
+            if let Some(id) = self.edge_id(position) {
+                self.edge_attributes.remove(&id);
+            }
+        }
+        self.edge_ids.retain(&kept);
+
+        let mut removed_edges = vec![];
+        let mut kept = kept.into_iter();
+        self.edges.retain(|edge| {
+            let keep = kept.next().unwrap_or(true);
+            if !keep {
+                removed_edges.push(*edge);
+            }
+            keep
+        });
+
+        let renumber = |node_index: u32| if node_index > index { node_index - 1 } else { node_index };
+        for edge in &mut self.edges {
+            edge.from_index = renumber(edge.from_index);
+            edge.to_index = renumber(edge.to_index);
+        }
+
+        if let Some(labels) = &mut self.labels {
+            labels.remove(index);
+        }
+
+        self.node_attributes.remove(&node);
+        self.node_ids.remove(index as usize - 1, self.nodes_count as usize);
+        self.nodes_count -= 1;
+        self.adjacency.invalidate();
+
+        Ok(removed_edges)
+    }
+
+    /// Adds edge connecting given nodes and returns its handle
+    ///
+    /// Returns [`EditGraphError::UnknownNode`] if any of the nodes doesn't belong to the graph.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - node, where edge starts
+    /// * `to` - node, where edge ends
+    /// * `weight` - edge weight
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, weight: W) -> EditGraphResult<EdgeId> {
+        let from_index = self.node_index(from).ok_or(EditGraphError::UnknownNode(from))?;
+        let to_index = self.node_index(to).ok_or(EditGraphError::UnknownNode(to))?;
+
+        let id = self.edge_ids.push(self.edges.len());
+        self.edges.push(Edge::new(from_index, to_index, weight));
+        self.adjacency.invalidate();
+
+        Ok(EdgeId(id))
+    }
+
+    /// Removes edge and returns it, or [`EditGraphError::UnknownEdge`] if there is no such edge
+    ///
+    /// # Arguments
+    ///
+    /// * `edge` - handle of the edge
+    pub fn remove_edge(&mut self, edge: EdgeId) -> EditGraphResult<Edge<W>> {
+        let position = self.edge_position(edge).ok_or(EditGraphError::UnknownEdge(edge))?;
+
+        self.edge_attributes.remove(&edge);
+        self.edge_ids.remove(position, self.edges.len());
+        self.adjacency.invalidate();
+
+        Ok(self.edges.remove(position))
+    }
+
+    /// Changes weight of the edge and returns the previous one, or [`EditGraphError::UnknownEdge`]
+    /// if there is no such edge
+    ///
+    /// # Arguments
+    ///
+    /// * `edge` - handle of the edge
+    /// * `weight` - new weight of the edge
+    pub fn set_weight(&mut self, edge: EdgeId, weight: W) -> EditGraphResult<W> {
+        let position = self.edge_position(edge).ok_or(EditGraphError::UnknownEdge(edge))?;
+
+        self.adjacency.invalidate();
+
+        Ok(std::mem::replace(&mut self.edges[position].weight, weight))
+    }
+
+    fn edge_position(&self, edge: EdgeId) -> Option<usize> {
+        self.edge_ids.position(edge.0, self.edges.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph() -> Graph {
+        "4 4 directed
+            1 2 100
+            2 3 80
+            3 4 50
+            4 1 70"
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn ids_are_stable_after_removal() -> EditGraphResult<()> {
+        let mut graph = graph();
+        let ids = (1..=4).map(|index| graph.node_id(index).unwrap()).collect::<Vec<_>>();
+        let last_edge = graph.edge_id(3).unwrap();
+
+        graph.remove_edge(graph.edge_id(0).unwrap())?;
+        let removed_edges = graph.remove_node(ids[1])?;
+
+        assert_eq!(removed_edges, vec![Edge::new(2, 3, 80)]);
+        assert_eq!(graph.node_index(ids[0]), Some(1));
+        assert_eq!(graph.node_index(ids[1]), None);
+        assert_eq!(graph.node_index(ids[3]), Some(3));
+        assert_eq!(graph.edges, vec![Edge::new(2, 3, 50), Edge::new(3, 1, 70)]);
+        assert_eq!(graph.edge(last_edge), Some(&Edge::new(3, 1, 70)));
+        Ok(())
+    }
+
+    #[test]
+    fn removing_node_with_many_edges() -> EditGraphResult<()> {
+        let mut graph = graph();
+        let edges = (0..4)
+            .map(|position| graph.edge_id(position).unwrap())
+            .collect::<Vec<_>>();
+
+        let removed_edges = graph.remove_node(graph.node_id(1).unwrap())?;
+
+        assert_eq!(removed_edges, vec![Edge::new(1, 2, 100), Edge::new(4, 1, 70)]);
+        assert_eq!(graph.edges, vec![Edge::new(1, 2, 80), Edge::new(2, 3, 50)]);
+        assert_eq!(graph.edge(edges[0]), None);
+        assert_eq!(graph.edge(edges[2]), Some(&Edge::new(2, 3, 50)));
+        assert_eq!(graph.edge(edges[3]), None);
+        Ok(())
+    }
+
+    #[test]
+    fn removed_ids_are_not_reused() -> EditGraphResult<()> {
+        let mut graph = graph();
+        let first_edge = graph.edge_id(0).unwrap();
+        let last_node = graph.node_id(4).unwrap();
+
+        graph.remove_edge(first_edge)?;
+        graph.remove_node(last_node)?;
+        let new_edge = graph.add_edge(graph.node_id(1).unwrap(), graph.node_id(3).unwrap(), 10)?;
+        let new_node = graph.add_node();
+
+        assert_ne!(new_edge, first_edge);
+        assert_ne!(new_node, last_node);
+        assert_eq!(graph.edge(first_edge), None);
+        assert_eq!(graph.edge(new_edge), Some(&Edge::new(1, 3, 10)));
+        assert_eq!(graph.node_index(new_node), Some(4));
+        Ok(())
+    }
+
+    #[test]
+    fn adjacency_is_updated() -> EditGraphResult<()> {
+        let mut graph = graph();
+        assert_eq!(graph.neighbours(1).collect::<Vec<_>>(), vec![(2, 100)]);
+
+        let edge = graph.edge_id(0).unwrap();
+        graph.set_weight(edge, 30)?;
+        assert_eq!(graph.neighbours(1).collect::<Vec<_>>(), vec![(2, 30)]);
+
+        graph.remove_edge(edge)?;
+        assert_eq!(graph.neighbours(1).count(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn undirected_edge_connects_both_ways() {
+        let undirected_graph: Graph = "2 1
+            1 2 100"
+            .parse()
+            .unwrap();
+        let (gate, pond) = (
+            undirected_graph.node_id(1).unwrap(),
+            undirected_graph.node_id(2).unwrap(),
+        );
+
+        assert!(undirected_graph.contains_edge(pond, gate));
+        assert!(!undirected_graph.contains_edge(gate, gate));
+        assert!(!graph().contains_edge(pond, gate));
+    }
+
+    #[test]
+    fn removing_labelled_node() -> EditGraphResult<()> {
+        let mut graph: Graph = "3 2 labels
+            gate pond 100
+            pond fountain 80"
+            .parse()
+            .unwrap();
+
+        graph.remove_node(graph.node_id(1).unwrap())?;
+
+        let labels = graph.labels().unwrap();
+        assert_eq!(labels.label(1), Some("pond"));
+        assert_eq!(labels.index("fountain"), Some(2));
+        assert_eq!(labels.index("gate"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_handles() {
+        let mut graph = graph();
+        let node = graph.node_id(2).unwrap();
+        let edge = graph.edge_id(1).unwrap();
+
+        graph.remove_node(node).unwrap();
+
+        assert_eq!(graph.remove_node(node), Err(EditGraphError::UnknownNode(node)));
+        assert_eq!(graph.set_weight(edge, 10), Err(EditGraphError::UnknownEdge(edge)));
+        assert_eq!(
+            EditGraphError::UnknownNode(node).to_string(),
+            "there is no node #2 in the graph"
+        );
+    }
+}
@@ -1,4 +1,3 @@
-use crate::Edge;
 use parse_display::Display;
 use thiserror::Error;
 
@@ -26,9 +25,9 @@ pub type BuildGraphResult<T, E = BuildGraphError> = std::result::Result<T, E>;
 /// assert!(maybe_graph.is_err());
 /// assert_eq!(maybe_graph.unwrap_err().to_string(),
 ///     BuildGraphError::ErrorInGraphDescriptionFile {
-///     line_no: 2,
+///     line_no: 3,
 ///     error: Box::from(BuildGraphError::from(AddingEdgeError::WrongToIndex {
-///         edge: "2 4 100".parse().unwrap(),
+///         edge: "2 4 100".to_owned(),
 ///         nodes_count: 3
 ///     })),
 /// }.to_string());
@@ -82,10 +81,41 @@ pub enum BuildGraphError {
     #[error("error parsing graph parameters - {0}")]
     GraphParametersParsingError(GraphParametersParsingError),
 
+    /// Line in DIMACS file isn't valid (apart from invalid arcs and counts)
+    #[error("error parsing DIMACS file - {0}")]
+    DimacsParsingError(DimacsParsingError),
+
+    /// Binary graph file is corrupted or was written for a different weight type
+    #[error("error reading binary graph - {0}")]
+    BinaryFormatError(BinaryFormatError),
+
+    /// Node or edge attributes are invalid or given to a node or edge that doesn't belong to the graph
+    #[error("error in attributes - {0}")]
+    AttributeError(AttributeError),
+
+    /// Graph has more node labels than nodes
+    #[error("there are {labels_count} node labels, but only {nodes_count} nodes in graph")]
+    TooManyNodeLabels {
+        /// Number of given labels
+        labels_count: usize,
+
+        /// Declared number of nodes in the graph
+        nodes_count: u32,
+    },
+
+    /// The same label is given to more than one node
+    #[error("label `{0}` is given to more than one node")]
+    DuplicateNodeLabel(String),
+
+    /// Transformation needs a graph without self-loops and parallel edges (see [`crate::Graph::complement`])
+    #[error("graph contains self-loops or parallel edges, but simple graph is required")]
+    GraphNotSimple,
+
     /// Indicates, which line in input file is invalid and what's the error
     #[error("error in line {line_no}: {error}")]
     ErrorInGraphDescriptionFile {
-        ///Number of line that caused the error
+        /// Number of line that caused the error, in the input (starting from 1, header, blank
+        /// and comment lines are counted)
         line_no: usize,
 
         /// Variant of the error
@@ -115,6 +145,24 @@ impl From<GraphParametersParsingError> for BuildGraphError {
     }
 }
 
+impl From<DimacsParsingError> for BuildGraphError {
+    fn from(e: DimacsParsingError) -> Self {
+        BuildGraphError::DimacsParsingError(e)
+    }
+}
+
+impl From<BinaryFormatError> for BuildGraphError {
+    fn from(e: BinaryFormatError) -> Self {
+        BuildGraphError::BinaryFormatError(e)
+    }
+}
+
+impl From<AttributeError> for BuildGraphError {
+    fn from(e: AttributeError) -> Self {
+        BuildGraphError::AttributeError(e)
+    }
+}
+
 // -----------------------------------------------------------------------------
 
 /// Enum with errors related to parsing graph edge parameters
@@ -132,6 +180,10 @@ pub enum ParsingEdgeError {
     #[display("missing `weight` field")]
     MissingWeightField,
 
+    /// Line contains something more after `weight` value (and it isn't a comment)
+    #[display("unexpected token after `weight` field: `{0}`")]
+    UnexpectedTrailingToken(String),
+
     /// Non-integer value as `from_index`
     #[display("from_index must be an integer, but it is: `{0}`")]
     FromIndexValueMustBeInteger(String),
@@ -140,45 +192,132 @@ pub enum ParsingEdgeError {
     #[display("to_index must be an integer, but it is: `{0}`")]
     ToIndexValueMustBeInteger(String),
 
-    /// Non-integer value as `weight`
-    #[display("weight must be an integer, but it is: `{0}`")]
+    /// Value of `weight` can't be parsed to the weight type (e.g. non-integer value for integer weights)
+    #[display("weight must be a number, but it is: `{0}`")]
     WeightValueMustBeInteger(String),
 }
 
 // -----------------------------------------------------------------------------
 
+/// Enum with errors related to node and edge attributes (see [`crate::Attributes`])
+#[derive(Debug, Display, PartialEq)]
+pub enum AttributeError {
+    /// Attribute isn't written as `name=value` or contains invalid escape sequence
+    #[display("attribute must look like `name=value`, but it is: `{0}`")]
+    InvalidAttribute(String),
+
+    /// Node line of graph without labels describes node by non-integer value
+    #[display("node index must be an integer, but it is: `{0}`")]
+    NodeIndexValueMustBeInteger(String),
+
+    /// Attributes are given to node, which doesn't belong to the graph
+    #[display("`node {node}` doesn't match any of `{nodes_count}` nodes in graph !")]
+    UnknownNode {
+        /// Index or label of the node
+        node: String,
+
+        /// Declared number of nodes in the graph
+        nodes_count: u32,
+    },
+
+    /// Attributes are given to edge, which doesn't belong to the graph
+    #[display(
+        "attributes are given to edge at position {position}, but there are only {edges_count} edges in graph !"
+    )]
+    UnknownEdge {
+        /// Position of the edge
+        position: usize,
+
+        /// Number of edges in the graph
+        edges_count: usize,
+    },
+}
+
+// -----------------------------------------------------------------------------
+
 /// Enum with errors related to adding an edge to the graph
+///
+/// Edge user is trying to add is written the same way as in graph description file
+/// (using node labels if the graph has them)
 #[derive(Debug, Display)]
 pub enum AddingEdgeError {
     /// Graph already contains declared number of edges and can't add new edge
-    #[display("max allowed count of edges is {edges_count} but you are trying to add a new edge {edge:?}")]
+    #[display("max allowed count of edges is {edges_count} but you are trying to add a new edge `{edge}`")]
     TooManyEdges {
         /// Declared number of edges
         edges_count: usize,
 
         /// [`crate::Edge`] user is trying to add
-        edge: Edge,
+        edge: String,
+    },
+
+    /// Edge contains node index 0, but nodes are numbered from 1 (see [`crate::IndexBase`])
+    #[display("`{edge}` contains node index 0, but nodes are numbered from 1 !")]
+    ZeroIndex {
+        /// [`crate::Edge`] user is trying to add
+        edge: String,
     },
 
     /// `from_index` field value is greater than number of nodes in the graph
-    #[display("{edge:?} from_index field value is greater than nodes count `{nodes_count}` in graph !")]
+    #[display("`{edge}` from_index field value is greater than nodes count `{nodes_count}` in graph !")]
     WrongFromIndex {
         /// [`crate::Edge`] user is trying to add
-        edge: Edge,
+        edge: String,
 
         /// Declared number of nodes in the graph
         nodes_count: u32,
     },
 
     /// `to_index` field value is greater than number of nodes in the graph
-    #[display("{edge:?} to_index field value is greater than nodes count `{nodes_count}` in graph !")]
+    #[display("`{edge}` to_index field value is greater than nodes count `{nodes_count}` in graph !")]
     WrongToIndex {
         /// [`crate::Edge`] user is trying to add
-        edge: Edge,
+        edge: String,
 
         /// Declared number of nodes in the graph
         nodes_count: u32,
     },
+
+    /// Edge contains new node label, but graph already has declared number of labelled nodes
+    #[display("`{edge}` contains node `{label}`, but all `{nodes_count}` nodes in graph already have labels !")]
+    TooManyNodeLabels {
+        /// [`crate::Edge`] user is trying to add
+        edge: String,
+
+        /// Label that can't be added
+        label: String,
+
+        /// Declared number of nodes in the graph
+        nodes_count: u32,
+    },
+
+    /// Edge has negative weight, but graph builder doesn't allow it
+    #[display("`{edge}` has negative weight, which isn't allowed in this graph !")]
+    NegativeWeight {
+        /// [`crate::Edge`] user is trying to add
+        edge: String,
+    },
+
+    /// Edge connects node with itself, but [`crate::ValidationPolicy`] doesn't allow self-loops
+    #[display("`{edge}` connects node with itself, which isn't allowed in this graph !")]
+    SelfLoop {
+        /// [`crate::Edge`] user is trying to add
+        edge: String,
+    },
+
+    /// Edge connects the same nodes as already added edge, but [`crate::ValidationPolicy`] doesn't allow parallel edges
+    #[display("`{edge}` connects the same nodes as another edge, which isn't allowed in this graph !")]
+    ParallelEdge {
+        /// [`crate::Edge`] user is trying to add
+        edge: String,
+    },
+
+    /// Edge uses node labels, but graph wasn't declared with `labels` option
+    #[display("`{edge}` uses node labels, but graph nodes aren't labelled !")]
+    NodeLabelsNotDeclared {
+        /// [`crate::Edge`] user is trying to add
+        edge: String,
+    },
 }
 
 // -----------------------------------------------------------------------------
@@ -201,4 +340,92 @@ pub enum GraphParametersParsingError {
     /// Non-integer value as `edges_count`
     #[display("edges count must be an integer, but it is: `{0}`")]
     EdgesCountValueIsNotInteger(String),
+
+    /// Value following nodes and edges count in first line isn't any of known graph options
+    #[display("graph option must be `directed`, `undirected` or `labels`, but it is: `{0}`")]
+    UnknownGraphOption(String),
+}
+
+// -----------------------------------------------------------------------------
+
+/// Enum with errors specific to DIMACS shortest path (`.gr`) format
+#[derive(Debug, Display)]
+pub enum DimacsParsingError {
+    /// Arc line appears before the problem line or there is no problem line at all
+    #[display("missing problem line `p sp <nodes count> <arcs count>`")]
+    MissingProblemLine,
+
+    /// File contains more than one problem line
+    #[display("unexpected problem line, graph parameters are already declared")]
+    UnexpectedProblemLine,
+
+    /// Problem line doesn't contain problem type, nodes count and arcs count
+    #[display("problem line must look like `p sp <nodes count> <arcs count>`, but it is: `{0}`")]
+    InvalidProblemLine(String),
+
+    /// Problem type isn't `sp` (shortest path)
+    #[display("only `sp` problem is supported, but it is: `{0}`")]
+    UnsupportedProblem(String),
+
+    /// Line starts with something else than `c`, `p` or `a`
+    #[display("line must start with `c`, `p` or `a`, but it starts with: `{0}`")]
+    UnknownLineDescriptor(String),
+}
+
+// -----------------------------------------------------------------------------
+
+/// Enum with errors specific to binary graph format (see [`crate::write_binary`])
+#[derive(Debug, Display)]
+pub enum BinaryFormatError {
+    /// Input doesn't start with magic bytes of binary graph format
+    #[display("input doesn't start with binary graph header")]
+    InvalidMagic,
+
+    /// Input was written in a version of the format that isn't supported
+    #[display("binary graph format version {0} isn't supported")]
+    UnsupportedVersion(u16),
+
+    /// Input ends before all declared data is read
+    #[display("unexpected end of input")]
+    UnexpectedEnd,
+
+    /// Checksum stored in the input doesn't match its content
+    #[display("checksum mismatch, stored checksum is {expected:#010x}, but content checksum is {actual:#010x}")]
+    ChecksumMismatch {
+        /// Checksum stored at the end of input
+        expected: u32,
+
+        /// Checksum calculated from the content
+        actual: u32,
+    },
+
+    /// Weights in the input have different type than weights of the built graph
+    #[display("graph weights were written with type tag {found}, but type tag {expected} is expected")]
+    WeightTypeMismatch {
+        /// Type tag of the weight of the built graph (see [`crate::Weight::BINARY_TAG`])
+        expected: u8,
+
+        /// Type tag stored in the input
+        found: u8,
+    },
+
+    /// Byte describing graph kind is neither undirected (0) nor directed (1)
+    #[display("invalid graph kind byte `{0}`")]
+    InvalidGraphKind(u8),
+
+    /// Edge weight can't be decoded
+    #[display("invalid weight of edge {0}")]
+    InvalidWeight(usize),
+
+    /// Node label isn't valid UTF-8
+    #[display("invalid label of node {0}")]
+    InvalidLabel(u32),
+
+    /// Node or edge attribute has unknown value type or its text isn't valid UTF-8
+    #[display("invalid attribute of {0}")]
+    InvalidAttribute(String),
+
+    /// There are bytes between the graph data and the checksum
+    #[display("unexpected bytes after graph data")]
+    UnexpectedTrailingBytes,
 }
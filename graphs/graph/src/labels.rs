@@ -0,0 +1,145 @@
+use crate::{BuildGraphError, BuildGraphResult};
+use std::collections::HashMap;
+
+/// Two-way map between names of the nodes (labels) and their indices
+///
+/// Indices are given to labels in order of their insertion, starting from 1
+///
+/// # Example
+/// ```
+/// use graph::Graph;
+///
+/// let graph: Graph = "3 2 labels
+///     gate pond_north 120
+///     pond_north fountain 80"
+///     .parse()
+///     .unwrap();
+///
+/// let labels = graph.labels().unwrap();
+///
+/// assert_eq!(labels.len(), 3);
+/// assert_eq!(labels.index("pond_north"), Some(2));
+/// assert_eq!(labels.label(3), Some("fountain"));
+/// assert_eq!(labels.index("garage"), None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NodeLabels {
+    /// Label of node with index `i` is stored at position `i - 1`
+    labels: Vec<String>,
+
+    /// Index of the node with given label
+    indices: HashMap<String, u32>,
+}
+
+impl NodeLabels {
+    /// Creates empty map
+    pub fn new() -> NodeLabels {
+        NodeLabels::default()
+    }
+
+    /// Returns index of the node with given label (or None if there is no such label)
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - name of the node
+    pub fn index(&self, label: &str) -> Option<u32> {
+        self.indices.get(label).copied()
+    }
+
+    /// Returns label of the node with given index (or None if node has no label)
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - index of the node
+    pub fn label(&self, index: u32) -> Option<&str> {
+        index
+            .checked_sub(1)
+            .and_then(|position| self.labels.get(position as usize))
+            .map(String::as_str)
+    }
+
+    /// Number of labelled nodes
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    /// Checks if there are no labels
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Returns iterator over pairs (`index`, `label`) ordered by index
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &str)> {
+        self.labels
+            .iter()
+            .enumerate()
+            .map(|(position, label)| (position as u32 + 1, label.as_str()))
+    }
+
+    /// Returns index of the node with given label, label that wasn't known before gets next free index
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - name of the node
+    pub fn insert(&mut self, label: &str) -> u32 {
+        if let Some(index) = self.index(label) {
+            return index;
+        }
+
+        self.labels.push(label.to_owned());
+        let index = self.labels.len() as u32;
+        self.indices.insert(label.to_owned(), index);
+
+        index
+    }
+
+    /// Returns labels of given nodes, node at position `i` of `indices` gets index `i + 1`
+    ///
+    /// Labels are given to nodes in order of their indices, so they stop at the first node without label.
+    ///
+    /// # Arguments
+    ///
+    /// * `indices` - indices of the kept nodes, in order of their new indices
+    pub fn restricted_to(&self, indices: &[u32]) -> NodeLabels {
+        let mut kept_labels = NodeLabels::new();
+        for label in indices.iter().map_while(|index| self.label(*index)) {
+            kept_labels.insert(label);
+        }
+
+        kept_labels
+    }
+
+    // removes label of the node with given index, nodes with greater indices get indices smaller by 1
+    pub(crate) fn remove(&mut self, index: u32) {
+        let position = index as usize - 1;
+        if position >= self.labels.len() {
+            return;
+        }
+
+        let label = self.labels.remove(position);
+        self.indices.remove(&label);
+        for (position, label) in self.labels.iter().enumerate().skip(position) {
+            self.indices.insert(label.clone(), position as u32 + 1);
+        }
+    }
+}
+
+// labels are given to nodes in order of their indices
+pub(crate) fn node_labels(labels: Vec<String>, nodes_count: u32) -> BuildGraphResult<NodeLabels> {
+    if labels.len() > nodes_count as usize {
+        return Err(BuildGraphError::TooManyNodeLabels {
+            labels_count: labels.len(),
+            nodes_count,
+        });
+    }
+
+    let mut node_labels = NodeLabels::new();
+    for label in labels {
+        if node_labels.index(&label).is_some() {
+            return Err(BuildGraphError::DuplicateNodeLabel(label));
+        }
+        node_labels.insert(&label);
+    }
+
+    Ok(node_labels)
+}
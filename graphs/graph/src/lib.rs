@@ -1,4 +1,4 @@
-//! Implementation of directed graph structure
+//! Implementation of directed and undirected graph structure
 //!
 //! Result type for functions in this crate is [`crate::errors::BuildGraphResult`]
 //!
@@ -13,33 +13,85 @@
 //!     .parse()
 //!     .unwrap();
 //!
-//! assert_eq!(graph.nodes_count, 4);
-//! assert_eq!(graph.edges.len(), 3);
+//! assert_eq!(graph.nodes_count(), 4);
+//! assert_eq!(graph.edges().len(), 3);
 //!
-//! let second_edge = graph.edges[1];
+//! let second_edge = graph.edges()[1];
 //!
 //! assert_eq!(second_edge.from_index, 1);
 //! assert_eq!(second_edge.to_index, 3);
 //! assert_eq!(second_edge.weight, 220);
 //! ```
+//!
+//! # Features
+//!
+//! * `mmap` - binary graph files (see [`write_binary`]) are memory-mapped instead of being read into memory
+//! * `parallel` - text graph files are parsed on a thread pool (see `build_graph_parallel`)
+//! * `serde` - serialization of [`Graph`], [`Edge`] and [`GraphParameters`] (see `GraphData`)
 
 // extern these crates only when running tests
 #[cfg(test)]
 extern crate test_case;
 
 mod adjacency_list;
-mod dfs;
+mod attributes;
+mod binary;
+mod components;
+mod diagnostics;
+mod dimacs;
+mod dot;
+mod editing;
 mod errors;
+mod labels;
+mod normalize;
+#[cfg(feature = "parallel")]
+mod parallel;
 mod reader;
+#[cfg(feature = "serde")]
+mod serialization;
+mod stats;
 mod structures;
+mod subgraph;
+mod transform;
+mod traversal;
+mod validation;
+mod weight;
 
-pub use crate::adjacency_list::{adjacency_list, AdjacencyList};
+pub use crate::adjacency_list::{AdjacencyList, Neighbours};
+pub use crate::attributes::{AttributeValue, Attributes};
+pub use crate::binary::write_binary;
+pub use crate::components::{Components, IncrementalComponents};
+pub use crate::diagnostics::{build_graph_with_diagnostics, Diagnostic, Diagnostics, Location};
+pub use crate::dimacs::write_dimacs;
+pub use crate::dot::DotWriter;
+pub use crate::editing::{EdgeId, EditGraphError, EditGraphResult, NodeId};
 pub use crate::errors::{
     AddingEdgeError,
+    AttributeError,
+    BinaryFormatError,
     BuildGraphError,
     BuildGraphResult,
+    DimacsParsingError,
     GraphParametersParsingError,
     ParsingEdgeError,
 };
-pub use crate::reader::build_graph;
-pub use crate::structures::{Edge, EdgeDescription, Graph, GraphBuilder, GraphParameters};
+pub use crate::labels::NodeLabels;
+pub use crate::normalize::{MergeRule, NormalizationError, NormalizationReport};
+#[cfg(feature = "parallel")]
+pub use crate::parallel::build_graph_parallel;
+pub use crate::reader::{
+    build_graph,
+    build_graph_in_format,
+    build_graph_with_options,
+    DataSource,
+    GraphFormat,
+    ReadOptions,
+};
+#[cfg(feature = "serde")]
+pub use crate::serialization::GraphData;
+pub use crate::stats::{DegreeStats, GraphStats};
+pub use crate::structures::{Edge, EdgeDescription, Graph, GraphBuilder, GraphKind, GraphParameters, IndexBase};
+pub use crate::subgraph::Subgraph;
+pub use crate::traversal::{Bfs, Dfs, DfsEvent, DfsEvents, DfsVisitor};
+pub use crate::validation::{EdgesCountPolicy, ValidationPolicy};
+pub use crate::weight::{OrderedFloat, Weight};
@@ -0,0 +1,226 @@
+use crate::structures::nodes_pair;
+use crate::{Edge, Graph, Weight};
+use parse_display::{Display, FromStr};
+use std::collections::hash_map::{Entry, HashMap};
+use thiserror::Error;
+
+/// Errors returned by [`Graph::normalize`]
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum NormalizationError {
+    /// Sum of weights of parallel edges doesn't fit in the weight type
+    #[error("weight of edge `{edge}` can't be added to weights of parallel edges, the sum overflows the weight type")]
+    WeightOverflow {
+        /// Parallel edge, which couldn't be merged, written the same way as in graph description file
+        /// (see [`Graph::describe_edge`])
+        edge: String,
+    },
+}
+
+/// Rule choosing weight of the edge, which replaces parallel edges in [`Graph::normalize`]
+#[derive(Copy, Clone, Debug, Display, FromStr, PartialEq, Eq)]
+#[display(style = "lowercase")]
+pub enum MergeRule {
+    /// The smallest weight of the parallel edges
+    Min,
+
+    /// The greatest weight of the parallel edges
+    Max,
+
+    /// Sum of weights of the parallel edges
+    Sum,
+}
+
+impl MergeRule {
+    // returns None if the sum overflows
+    fn merge<W: Weight>(self, weight: W, other: W) -> Option<W> {
+        match self {
+            MergeRule::Min => Some(weight.min(other)),
+            MergeRule::Max => Some(weight.max(other)),
+            MergeRule::Sum => weight.checked_add(other),
+        }
+    }
+}
+
+/// Edges removed by [`Graph::normalize`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct NormalizationReport<W = i32> {
+    /// Removed self-loops, in order of their positions in the graph
+    pub self_loops: Vec<Edge<W>>,
+
+    /// Parallel edges merged into the first edge connecting the same nodes, in order of their positions in the graph
+    pub merged_edges: Vec<Edge<W>>,
+}
+
+impl<W> NormalizationReport<W> {
+    /// Returns number of all removed edges
+    pub fn removed_count(&self) -> usize {
+        self.self_loops.len() + self.merged_edges.len()
+    }
+
+    /// Checks if graph was already simple, so nothing was removed
+    pub fn is_empty(&self) -> bool {
+        self.removed_count() == 0
+    }
+}
+
+impl<W: Weight> Graph<W> {
+    /// Makes the graph simple, removing self-loops and merging parallel edges
+    ///
+    /// Parallel edges are edges connecting the same nodes (in the same direction, if graph is directed).
+    /// The first of them stays in the graph (with its handle and attributes) and gets weight chosen by `rule`,
+    /// the other ones are removed. Order of the remaining edges doesn't change.
+    ///
+    /// Returns [`NormalizationReport`] listing all removed edges or [`NormalizationError`]
+    /// if merged weight overflows the weight type (then the graph isn't changed).
+    ///
+    /// # Example
+    /// ```
+    /// use graph::{Graph, MergeRule};
+    ///
+    /// let mut graph: Graph = "3 5
+    ///     1 2 13
+    ///     1 3 1
+    ///     2 1 4
+    ///     1 1 11
+    ///     3 2 2"
+    ///     .parse()
+    ///     .unwrap();
+    ///
+    /// let report = graph.normalize(MergeRule::Min).unwrap();
+    ///
+    /// assert_eq!(report.self_loops, vec!["1 1 11".parse().unwrap()]);
+    /// assert_eq!(report.merged_edges, vec!["2 1 4".parse().unwrap()]);
+    /// assert_eq!(graph.edges(), vec![
+    ///     "1 2 4".parse().unwrap(),
+    ///     "1 3 1".parse().unwrap(),
+    ///     "3 2 2".parse().unwrap(),
+    /// ]);
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `rule` - rule choosing weight of the edge replacing parallel edges
+    pub fn normalize(&mut self, rule: MergeRule) -> Result<NormalizationReport<W>, NormalizationError> {
+        let mut report = NormalizationReport {
+            self_loops:   vec![],
+            merged_edges: vec![],
+        };
+
+        // weights are merged into a copy, so the graph isn't changed if merging fails
+        let mut weights = self.edges.iter().map(|edge| edge.weight).collect::<Vec<_>>();
+        let mut kept = vec![true; self.edges.len()];
+        let mut first_positions = HashMap::new();
+        for (position, kept) in kept.iter_mut().enumerate() {
+            let edge = self.edges[position];
+
+            if edge.from_index == edge.to_index {
+                report.self_loops.push(edge);
+                *kept = false;
+                continue;
+            }
+
+            match first_positions.entry(nodes_pair(&edge, self.kind)) {
+                Entry::Vacant(entry) => {
+                    entry.insert(position);
+                }
+                Entry::Occupied(entry) => {
+                    let first_weight = &mut weights[*entry.get()];
+                    *first_weight =
+                        rule.merge(*first_weight, edge.weight)
+                            .ok_or_else(|| NormalizationError::WeightOverflow {
+                                edge: self.describe_edge(&edge),
+                            })?;
+
+                    report.merged_edges.push(edge);
+                    *kept = false;
+                }
+            }
+        }
+
+        if report.is_empty() {
+            return Ok(report);
+        }
+
+        for (edge, weight) in self.edges.iter_mut().zip(weights) {
+            edge.weight = weight;
+        }
+
+        for (position, _) in kept.iter().enumerate().filter(|(_, kept)| !**kept) {
+            if let Some(id) = self.edge_id(position) {
+                self.edge_attributes.remove(&id);
+            }
+        }
+        self.edge_ids.retain(&kept);
+
+        let mut kept = kept.into_iter();
+        self.edges.retain(|_| kept.next().unwrap_or(true));
+        self.adjacency.invalidate();
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{build_graph_with_options, ReadOptions, ValidationPolicy};
+    use test_case::test_case;
+
+    fn graph(input: &str) -> Graph {
+        build_graph_with_options(input, ReadOptions::new().with_policy(ValidationPolicy::permissive())).unwrap()
+    }
+
+    #[test_case(MergeRule::Min => 40; "min")]
+    #[test_case(MergeRule::Max => 100; "max")]
+    #[test_case(MergeRule::Sum => 220; "sum")]
+    fn merged_weight(rule: MergeRule) -> i32 {
+        let mut graph = graph("3 4\n1 2 100\n2 3 50\n2 1 40\n1 2 80");
+
+        let report = graph.normalize(rule).unwrap();
+
+        assert_eq!(report.merged_edges, vec![Edge::new(2, 1, 40), Edge::new(1, 2, 80)]);
+        assert_eq!(graph.edges.len(), 2);
+        graph.edges[0].weight
+    }
+
+    #[test]
+    fn directed_edges_in_opposite_directions_are_not_parallel() {
+        let mut graph = graph("2 3 directed\n1 2 100\n2 1 40\n2 2 10");
+
+        let report = graph.normalize(MergeRule::Min).unwrap();
+
+        assert_eq!(report.self_loops, vec![Edge::new(2, 2, 10)]);
+        assert!(report.merged_edges.is_empty());
+        assert_eq!(graph.edges, vec![Edge::new(1, 2, 100), Edge::new(2, 1, 40)]);
+    }
+
+    #[test]
+    fn handles_and_attributes_of_kept_edges() {
+        let mut graph = graph("3 4\n1 1 5 name=loop\n1 2 100 name=first\n2 1 40 name=second\n2 3 50 name=third");
+        let first = graph.edge_id(1).unwrap();
+        let second = graph.edge_id(2).unwrap();
+        let third = graph.edge_id(3).unwrap();
+
+        let report = graph.normalize(MergeRule::Sum).unwrap();
+
+        assert_eq!(report.removed_count(), 2);
+        assert_eq!(graph.edge(first), Some(&Edge::new(1, 2, 140)));
+        assert_eq!(graph.edge(second), None);
+        assert_eq!(graph.edge(third), Some(&Edge::new(2, 3, 50)));
+        assert_eq!(graph.to_string(), "3 2\n1 2 140 name=first\n2 3 50 name=third\n");
+        assert_eq!(graph.neighbours(2).collect::<Vec<_>>(), vec![(3, 50), (1, 140)]);
+    }
+
+    #[test_case("3 3\n1 2 2147483647\n2 3 10\n1 2 10", "1 2 10"; "indices")]
+    #[test_case("3 3 labels\ngate pond 2147483647\npond lake 10\ngate pond 10", "gate pond 10"; "labels")]
+    fn sum_overflow(input: &str, edge: &str) {
+        let mut graph = graph(input);
+        let expected = NormalizationError::WeightOverflow { edge: edge.to_owned() };
+
+        let actual = graph.normalize(MergeRule::Sum).unwrap_err();
+
+        assert_eq!(actual.to_string(), expected.to_string());
+        assert_eq!(graph.edges.len(), 3);
+        assert_eq!(graph.edges[0].weight, i32::MAX);
+    }
+}
@@ -0,0 +1,275 @@
+use crate::binary::build_graph_from_binary;
+use crate::reader::{build_graph_from_description, build_graph_from_lines, detect_format, in_line, is_binary};
+use crate::reader::{strip_comment, GraphDescriptionReader, ParsedLine};
+use crate::structures::GraphBuilder;
+use crate::{BuildGraphError, BuildGraphResult as Result, DataSource, Graph, GraphFormat, ReadOptions, Weight};
+use rayon::prelude::*;
+use std::io::{self, Read};
+
+/// Chunks smaller than this aren't worth sending to another thread
+const MIN_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Number of chunks given to every thread of the pool, so threads finishing early can take more work
+const CHUNKS_PER_THREAD: usize = 4;
+
+/// Builds a graph from the data source, parsing lines of native format on [`rayon`] thread pool
+/// (available with `parallel` feature)
+///
+/// Input is read into memory at once and split into line-aligned chunks. Lines of every chunk are parsed
+/// in parallel, then they are added to [`GraphBuilder`] in order of the input, so the graph and the errors
+/// (including line numbers in [`BuildGraphError::ErrorInGraphDescriptionFile`]) are the same
+/// as given by [`crate::build_graph_with_options`].
+///
+/// Graphs in DIMACS format and graphs with counts to infer (`?` in the header or a missing header)
+/// are read in one thread.
+///
+/// # Example
+/// ```
+/// use graph::{build_graph_parallel, Graph, ReadOptions};
+///
+/// let input = std::iter::once("1000 999".to_owned())
+///     .chain((1..1000).map(|index| format!("{} {} {}", index, index + 1, index * 10)))
+///     .collect::<Vec<_>>()
+///     .join("\n");
+///
+/// let graph: Graph = build_graph_parallel(input.as_str(), ReadOptions::new()).unwrap();
+///
+/// assert_eq!(graph.nodes_count(), 1000);
+/// assert_eq!(graph.edges()[998], "999 1000 9990".parse().unwrap());
+/// ```
+///
+/// # Arguments
+///
+/// * `data_source` - a reference to String, bytes, Path or PathBuf, or a [`DataSource`]
+/// * `options` - format of the input (detected if it's not given) and validation policy of the graph
+pub fn build_graph_parallel<'r, W, DS>(data_source: DS, options: ReadOptions) -> Result<Graph<W>>
+where
+    W: Weight,
+    DS: Into<DataSource<'r>>,
+{
+    match data_source.into() {
+        DataSource::String(s) => build_graph_from_bytes(s.as_bytes(), options),
+        DataSource::Bytes(bytes) => build_graph_from_bytes(bytes, options),
+        DataSource::File(filename) => build_graph_from_bytes(&std::fs::read(filename)?, options),
+        DataSource::Reader(mut reader) => {
+            let mut bytes = vec![];
+            reader.read_to_end(&mut bytes)?;
+            build_graph_from_bytes(&bytes, options)
+        }
+    }
+}
+
+// text input must be valid UTF-8
+fn build_graph_from_bytes<W: Weight>(bytes: &[u8], options: ReadOptions) -> Result<Graph<W>> {
+    if is_binary(bytes, options.format) {
+        return build_graph_from_binary(bytes, options.policy);
+    }
+
+    let input = std::str::from_utf8(bytes).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+    build_graph_from_text(input, options)
+}
+
+fn build_graph_from_text<W: Weight>(input: &str, options: ReadOptions) -> Result<Graph<W>> {
+    let format = match options.format {
+        Some(format) => format,
+        None => detect_format(&mut input.lines().map(Ok), &mut vec![])?,
+    };
+    if format == GraphFormat::Dimacs {
+        return build_graph_from_lines(input.lines().map(Ok), options.with_format(format));
+    }
+
+    // lines consumed by the reader are counted to find the beginning of edge lines
+    let mut header_length = 0;
+    let lines = input.split_inclusive('\n').inspect(|line| header_length += line.len());
+    let mut reader = GraphDescriptionReader::new(lines.map(Ok));
+    let (header, first_edge_line) = reader.graph_header(options.optional_header)?;
+    let mut first_line_no = reader.next_line_no() - usize::from(first_edge_line.is_some());
+
+    if header.nodes_count_inferred || header.edges_count_inferred {
+        return build_graph_from_description(input.lines().map(Ok), options);
+    }
+
+    // line without valid header is the first edge line
+    let edge_lines = &input[header_length - first_edge_line.map_or(0, str::len)..];
+
+    let policy = header.policy(options.policy);
+    let mut graph_builder = GraphBuilder::new(header.parameters)
+        .with_validation_policy(policy)
+        .with_index_base(options.index_base);
+    let labelled = graph_builder.is_labelled();
+
+    let chunks = split_into_chunks(edge_lines, rayon::current_num_threads() * CHUNKS_PER_THREAD)
+        .into_par_iter()
+        .map(|chunk| ParsedChunk::parse(chunk, labelled))
+        .collect::<Vec<_>>();
+
+    for chunk in chunks {
+        for (position, line) in chunk.lines {
+            line.add_to(&mut graph_builder)
+                .map_err(|error| in_line(first_line_no + position, error))?;
+        }
+        if let Some((position, error)) = chunk.error {
+            return Err(in_line(first_line_no + position, error));
+        }
+
+        first_line_no += chunk.lines_count;
+    }
+
+    graph_builder.build()
+}
+
+/// Splits `input` into about `chunks_count` chunks, every chunk (except the last one) ends with a newline
+///
+/// # Arguments
+///
+/// * `input` - lines of the graph description
+/// * `chunks_count` - expected number of chunks
+fn split_into_chunks(input: &str, chunks_count: usize) -> Vec<&str> {
+    let chunk_size = (input.len() / chunks_count.max(1)).max(MIN_CHUNK_SIZE);
+
+    let mut chunks = vec![];
+    let mut rest = input;
+    while !rest.is_empty() {
+        let end = match rest.as_bytes().iter().skip(chunk_size).position(|byte| *byte == b'\n') {
+            Some(position) => chunk_size + position + 1,
+            None => rest.len(),
+        };
+
+        let (chunk, next) = rest.split_at(end);
+        chunks.push(chunk);
+        rest = next;
+    }
+
+    chunks
+}
+
+/// Lines of a chunk parsed in one thread
+struct ParsedChunk<'a, W> {
+    /// Number of lines in the chunk
+    lines_count: usize,
+
+    /// Edge and node lines with their positions in the chunk
+    lines: Vec<(usize, ParsedLine<'a, W>)>,
+
+    /// The first invalid line (the following lines aren't parsed)
+    error: Option<(usize, BuildGraphError)>,
+}
+
+impl<'a, W: Weight> ParsedChunk<'a, W> {
+    // blank lines and comments are skipped, but they are counted in positions
+    fn parse(chunk: &'a str, labelled: bool) -> Self {
+        let mut lines = vec![];
+        let mut error = None;
+
+        for (position, line) in chunk.lines().enumerate() {
+            let line = strip_comment(line);
+            if line.is_empty() {
+                continue;
+            }
+
+            match ParsedLine::parse(line, labelled) {
+                Ok(parsed_line) => lines.push((position, parsed_line)),
+                Err(line_error) => {
+                    error = Some((position, line_error));
+                    break;
+                }
+            }
+        }
+
+        ParsedChunk {
+            lines_count: chunk.bytes().filter(|byte| *byte == b'\n').count(),
+            lines,
+            error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{build_graph_with_options, IndexBase, ValidationPolicy};
+    use test_case::test_case;
+
+    // edges of a cycle, with comments and blank lines between them
+    fn edge_lines(nodes_count: u32) -> Vec<String> {
+        (1..=nodes_count)
+            .map(|index| match index % 7 {
+                0 => format!("{} {} {} # road {}\n", index, index % nodes_count + 1, index, index),
+                3 => format!("\n{} {} {} lanes={}", index, index % nodes_count + 1, index, index % 3),
+                _ => format!("{} {} {}", index, index % nodes_count + 1, index),
+            })
+            .collect()
+    }
+
+    fn input(header: &str, lines: &[String]) -> String {
+        std::iter::once(header.to_owned())
+            .chain(lines.iter().cloned())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn assert_same_result(input: &str, options: ReadOptions) {
+        let expected = build_graph_with_options::<i32, _>(input, options);
+        let actual = build_graph_parallel::<i32, _>(input, options);
+
+        match (expected, actual) {
+            (Ok(expected), Ok(actual)) => assert_eq!(actual, expected),
+            (expected, actual) => assert_eq!(
+                actual.map(|_| ()).unwrap_err().to_string(),
+                expected.map(|_| ()).unwrap_err().to_string()
+            ),
+        }
+    }
+
+    #[test]
+    fn same_graph_as_sequential_reader() {
+        let lines = edge_lines(50_000);
+
+        assert_same_result(&input("# big cycle\n50000 50000", &lines), ReadOptions::new());
+    }
+
+    #[test_case(12_345, "12345 1 1 1"; "unexpected token")]
+    #[test_case(40_000, "40000 1 heavy"; "invalid weight")]
+    #[test_case(49_999, "49999 70000 1"; "unknown node")]
+    fn same_error_as_sequential_reader(position: usize, line: &str) {
+        let mut lines = edge_lines(50_000);
+        lines[position - 1] = line.to_owned();
+        // the earliest error must be reported, even if a later chunk is parsed first
+        lines[49_000] = "49001 1".to_owned();
+
+        assert_same_result(&input("# big cycle\n\n50000 50000", &lines), ReadOptions::new());
+    }
+
+    #[test]
+    fn missing_header() {
+        let lines = edge_lines(20_000);
+        let options = ReadOptions::new()
+            .with_optional_header(true)
+            .with_policy(ValidationPolicy::permissive());
+
+        assert_same_result(&lines.join("\n"), options);
+    }
+
+    #[test]
+    fn labelled_graph_with_zero_base() {
+        let lines = (0..30_000)
+            .map(|index| format!("gate{} gate{} {}", index, (index + 1) % 30_000, index))
+            .collect::<Vec<_>>();
+
+        assert_same_result(
+            &input("30000 30000 labels", &lines),
+            ReadOptions::new().with_index_base(IndexBase::Zero),
+        );
+    }
+
+    #[test]
+    fn chunks_are_line_aligned() {
+        let input = "1 2 3\n".repeat(MIN_CHUNK_SIZE / 3);
+
+        let chunks = split_into_chunks(&input, 16);
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks.concat(), input);
+        assert!(chunks.iter().all(|chunk| chunk.ends_with('\n')));
+    }
+}
@@ -1,14 +1,49 @@
-use super::structures::{Edge, Graph, GraphBuilder, GraphParameters};
-use crate::{BuildGraphError, BuildGraphResult as Result, GraphParametersParsingError};
+use super::structures::{Edge, EdgeDescription, Graph, GraphBuilder, GraphHeader, GraphParameters, IndexBase};
+use crate::attributes::{is_node_line, split_attributes};
+use crate::binary::{build_graph_from_binary, has_magic};
+use crate::dimacs::{build_graph_from_dimacs_lines, is_dimacs_line};
+use crate::{AttributeError, Attributes, BuildGraphError, BuildGraphResult as Result, GraphParametersParsingError};
+use crate::{ValidationPolicy, Weight};
+use parse_display::{Display, FromStr};
+use std::collections::HashSet;
 use std::convert::From;
 use std::convert::TryFrom;
-use std::fs;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
 use std::path::Path;
 use std::str::FromStr;
 
+/// Source of the graph description, read by [`build_graph`]
+///
+/// Text files and readers are parsed line by line, so the whole input is never kept in memory.
+/// Binary input (see [`crate::write_binary`]) is read at once.
 pub enum DataSource<'r> {
     String(&'r str),
+    Bytes(&'r [u8]),
     File(&'r Path),
+    Reader(Box<dyn BufRead + 'r>),
+}
+
+impl<'r> DataSource<'r> {
+    /// Creates data source reading graph description from any [`std::io::BufRead`] (e.g. locked stdin)
+    ///
+    /// # Example
+    /// ```
+    /// use graph::{build_graph, DataSource, Graph};
+    /// use std::io::Cursor;
+    ///
+    /// let input = Cursor::new("3 2\n1 2 100\n2 3 150\n");
+    /// let graph: Graph = build_graph(DataSource::from_reader(input)).unwrap();
+    ///
+    /// assert_eq!(graph.edges()[1], "2 3 150".parse().unwrap());
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - buffered reader with graph description
+    pub fn from_reader<R: BufRead + 'r>(reader: R) -> Self {
+        DataSource::Reader(Box::new(reader))
+    }
 }
 
 impl<'r> From<&'r str> for DataSource<'r> {
@@ -17,6 +52,12 @@ impl<'r> From<&'r str> for DataSource<'r> {
     }
 }
 
+impl<'r> From<&'r [u8]> for DataSource<'r> {
+    fn from(bytes: &'r [u8]) -> Self {
+        DataSource::Bytes(bytes)
+    }
+}
+
 impl<'r> From<&'r Path> for DataSource<'r> {
     fn from(filename: &'r Path) -> Self {
         DataSource::File(filename)
@@ -32,88 +73,540 @@ where
     }
 }
 
-/// Builds a directed graph from the data source with specific format
+/// Format of the graph description
+#[derive(Copy, Clone, Debug, Display, FromStr, PartialEq, Eq)]
+#[display(style = "lowercase")]
+pub enum GraphFormat {
+    /// Format described in [`build_graph_from_string`]
+    Native,
+
+    /// 9th DIMACS Challenge shortest path (`.gr`) format, always describing a directed graph
+    Dimacs,
+
+    /// Compact binary format written by [`crate::write_binary`]
+    Binary,
+}
+
+/// Builds a graph from the data source, detecting its format (see [`GraphFormat`])
+///
+/// Data source: string, file or reader with graph description
 ///
-/// Data source: string or file with graph description
+/// Input is in [`GraphFormat::Binary`], if it starts with the binary graph header (`GRPH`).
+/// Otherwise, it's in [`GraphFormat::Dimacs`], if its first line that isn't blank or a `#` comment
+/// starts with DIMACS line descriptor (`c`, `p` or `a`), otherwise it's in [`GraphFormat::Native`].
+///
+/// # Example
+/// ```
+/// use graph::{build_graph, Graph, GraphKind};
+///
+/// let graph: Graph = build_graph("c road network
+///     p sp 2 2
+///     a 1 2 100
+///     a 2 1 120")
+///     .unwrap();
+///
+/// assert_eq!(graph.kind(), GraphKind::Directed);
+/// assert_eq!(graph.edges()[1], "2 1 120".parse().unwrap());
+/// ```
 ///
 /// # Arguments
 ///
-/// * `data_source` - a reference to String, Path or PathBuf
-pub fn build_graph<'r, DS>(data_source: DS) -> Result<Graph>
+/// * `data_source` - a reference to String, bytes, Path or PathBuf, or a [`DataSource`]
+pub fn build_graph<'r, W, DS>(data_source: DS) -> Result<Graph<W>>
 where
+    W: Weight,
     DS: Into<DataSource<'r>>,
 {
-    let data_source: DataSource = data_source.into();
+    read_graph(data_source.into(), ReadOptions::new())
+}
+
+/// Builds a graph from the data source with given format
+///
+/// # Arguments
+///
+/// * `data_source` - a reference to String, bytes, Path or PathBuf, or a [`DataSource`]
+/// * `format` - [`GraphFormat`] of the graph description
+pub fn build_graph_in_format<'r, W, DS>(data_source: DS, format: GraphFormat) -> Result<Graph<W>>
+where
+    W: Weight,
+    DS: Into<DataSource<'r>>,
+{
+    read_graph(data_source.into(), ReadOptions::new().with_format(format))
+}
+
+/// Builds a graph from the data source with given [`ReadOptions`]
+///
+/// # Example
+/// ```
+/// use graph::{build_graph_with_options, Graph, ReadOptions, ValidationPolicy};
+///
+/// let graph: Graph = build_graph_with_options("3 1
+///     1 2 100", ReadOptions::new().with_policy(ValidationPolicy::permissive()))
+///     .unwrap();
+///
+/// assert_eq!(graph.nodes_count(), 3);
+/// assert_eq!(graph.edges().len(), 1);
+/// ```
+///
+/// # Arguments
+///
+/// * `data_source` - a reference to String, bytes, Path or PathBuf, or a [`DataSource`]
+/// * `options` - format of the input (detected if it's not given) and validation policy of the graph
+pub fn build_graph_with_options<'r, W, DS>(data_source: DS, options: ReadOptions) -> Result<Graph<W>>
+where
+    W: Weight,
+    DS: Into<DataSource<'r>>,
+{
+    read_graph(data_source.into(), options)
+}
+
+/// Options of reading a graph with [`build_graph_with_options`]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReadOptions {
+    /// Format of the input, detected if it's None (see [`build_graph`])
+    pub format: Option<GraphFormat>,
+
+    /// Checks done while building the graph
+    pub policy: ValidationPolicy,
+
+    /// Index of the first node in text formats (binary format always numbers nodes from 1)
+    pub index_base: IndexBase,
+
+    /// Tells if graph parameters may be missing in native format, so the input is a plain list of edges
+    ///
+    /// Graph without parameters is undirected and has no labels, counts of nodes and edges are inferred.
+    pub optional_header: bool,
+}
+
+impl ReadOptions {
+    /// Creates options detecting the format and using default [`ValidationPolicy`]
+    pub fn new() -> ReadOptions {
+        ReadOptions::default()
+    }
+
+    /// Sets format of the input
+    pub fn with_format(self, format: GraphFormat) -> ReadOptions {
+        ReadOptions {
+            format: Some(format),
+            ..self
+        }
+    }
+
+    /// Sets checks done while building the graph
+    pub fn with_policy(self, policy: ValidationPolicy) -> ReadOptions {
+        ReadOptions { policy, ..self }
+    }
+
+    /// Sets index of the first node in text formats
+    pub fn with_index_base(self, index_base: IndexBase) -> ReadOptions {
+        ReadOptions { index_base, ..self }
+    }
+
+    /// Sets if graph parameters may be missing in native format
+    ///
+    /// # Example
+    /// ```
+    /// use graph::{build_graph_with_options, Graph, ReadOptions};
+    ///
+    /// let graph: Graph = build_graph_with_options("1 2 100
+    ///     2 3 80", ReadOptions::new().with_optional_header(true))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(graph.nodes_count(), 3);
+    /// assert_eq!(graph.edges().len(), 2);
+    /// ```
+    pub fn with_optional_header(self, optional_header: bool) -> ReadOptions {
+        ReadOptions {
+            optional_header,
+            ..self
+        }
+    }
+}
+
+// format is detected if it's not given
+fn read_graph<W: Weight>(data_source: DataSource, options: ReadOptions) -> Result<Graph<W>> {
     match data_source {
-        DataSource::String(s) => build_graph_from_string(s),
-        DataSource::File(filename) => build_graph_from_file(filename),
+        DataSource::String(s) if is_binary(s.as_bytes(), options.format) => {
+            build_graph_from_binary(s.as_bytes(), options.policy)
+        }
+        DataSource::String(s) => build_graph_from_lines(s.lines().map(Ok), options),
+        DataSource::Bytes(bytes) => build_graph_from_bytes(bytes, options),
+        DataSource::File(filename) => build_graph_from_file(filename, options),
+        DataSource::Reader(reader) => build_graph_from_reader(reader, options),
     }
 }
 
-/// Builds a directed graph from txt file with specific format
+// text input must be valid UTF-8
+fn build_graph_from_bytes<W: Weight>(bytes: &[u8], options: ReadOptions) -> Result<Graph<W>> {
+    if is_binary(bytes, options.format) {
+        return build_graph_from_binary(bytes, options.policy);
+    }
+
+    let input = std::str::from_utf8(bytes).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+    build_graph_from_lines(input.lines().map(Ok), options)
+}
+
+/// Builds a graph from file
 ///
-/// File should be formatted as in [`build_graph_from_string`]
+/// Binary file is memory-mapped if `mmap` feature is enabled, otherwise it's read into memory at once.
 ///
 /// # Arguments
 ///
 /// * `filename` - path to file containing input
-fn build_graph_from_file<P: AsRef<Path>>(filename: P) -> Result<Graph> {
-    let filename = filename.as_ref();
-    let input = fs::read_to_string(filename)?;
-    build_graph_from_string(input.as_str())
+/// * `options` - format of the input (detected if it's None) and validation policy
+fn build_graph_from_file<W: Weight, P: AsRef<Path>>(filename: P, options: ReadOptions) -> Result<Graph<W>> {
+    let mut reader = BufReader::new(File::open(filename.as_ref())?);
+
+    if is_binary(reader.fill_buf()?, options.format) {
+        return build_graph_from_binary_file(reader, options.policy);
+    }
+
+    build_graph_from_reader(reader, options)
 }
 
-/// Builds a directed graph from string with specific format
+#[cfg(feature = "mmap")]
+fn build_graph_from_binary_file<W: Weight>(reader: BufReader<File>, policy: ValidationPolicy) -> Result<Graph<W>> {
+    // SAFETY: the file mustn't be modified while it's mapped, which can't be guaranteed for files
+    // shared with other processes. Any change of the content is detected by the checksum
+    // and all the data is copied to the graph before the map is dropped.
+    let map = unsafe { memmap2::Mmap::map(reader.get_ref())? };
+    build_graph_from_binary(&map, policy)
+}
+
+#[cfg(not(feature = "mmap"))]
+fn build_graph_from_binary_file<W: Weight>(mut reader: BufReader<File>, policy: ValidationPolicy) -> Result<Graph<W>> {
+    use std::io::Read;
+
+    // bytes already buffered while detecting the format are read first
+    let mut bytes = vec![];
+    reader.read_to_end(&mut bytes)?;
+    build_graph_from_binary(&bytes, policy)
+}
+
+/// Builds a graph from buffered reader, reading text formats line by line
+///
+/// # Arguments
+///
+/// * `reader` - buffered reader with graph description
+/// * `options` - format of the input (detected if it's None) and validation policy
+fn build_graph_from_reader<W: Weight, R: BufRead>(mut reader: R, options: ReadOptions) -> Result<Graph<W>> {
+    if is_binary(reader.fill_buf()?, options.format) {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes)?;
+        return build_graph_from_binary(&bytes, options.policy);
+    }
+
+    build_graph_from_lines(reader.lines().map(|line| line.map_err(BuildGraphError::from)), options)
+}
+
+// `start` is the beginning of the input, used to detect the format if it's not given
+pub(crate) fn is_binary(start: &[u8], format: Option<GraphFormat>) -> bool {
+    match format {
+        Some(format) => format == GraphFormat::Binary,
+        None => has_magic(start),
+    }
+}
+
+// lines read while detecting the format are given back to the parser, so line numbers don't change
+pub(crate) fn build_graph_from_lines<W, L, I>(mut lines: I, options: ReadOptions) -> Result<Graph<W>>
+where
+    W: Weight,
+    L: AsRef<str>,
+    I: Iterator<Item = Result<L>>,
+{
+    let mut read_lines = vec![];
+    let format = match options.format {
+        Some(format) => format,
+        None => detect_format(&mut lines, &mut read_lines)?,
+    };
+
+    let lines = read_lines.into_iter().map(Ok).chain(lines);
+    match format {
+        GraphFormat::Native => build_graph_from_description(lines, options),
+        GraphFormat::Dimacs => build_graph_from_dimacs_lines(lines, options),
+        GraphFormat::Binary => unreachable!("binary input isn't read line by line"),
+    }
+}
+
+// reads lines until the first one that isn't blank or a comment
+pub(crate) fn detect_format<L, I>(lines: &mut I, read_lines: &mut Vec<L>) -> Result<GraphFormat>
+where
+    L: AsRef<str>,
+    I: Iterator<Item = Result<L>>,
+{
+    for line in lines {
+        let line = line?;
+        let stripped_line = strip_comment(line.as_ref());
+
+        if stripped_line.is_empty() {
+            read_lines.push(line);
+            continue;
+        }
+
+        let format = if is_dimacs_line(stripped_line) {
+            GraphFormat::Dimacs
+        } else {
+            GraphFormat::Native
+        };
+
+        read_lines.push(line);
+        return Ok(format);
+    }
+
+    Ok(GraphFormat::Native)
+}
+
+/// Builds a graph from string with specific format
 ///
 /// First line of string should contain two positive integers - number of nodes in the graph (`nodes_count`)
-/// and number of edges in the graph (`edges_count`).
+/// and number of edges in the graph (`edges_count`), any of them might be `?` if it should be inferred
+/// from the edges. Counts are optionally followed by [`crate::GraphKind`] of the graph
+/// (`directed` or `undirected`, which is the default), `labels` option and `disconnected` option.
 ///
-/// Then, every line describes one of the `edges_count` edges and contains three integers:
+/// Then, every line describes one of the `edges_count` edges and contains three values:
 ///
 /// * First - Index of node where edge starts ([`Edge::from_index`])
 /// * Second - Index of node where edge ends ([`Edge::to_index`])
-/// * Third - weight of the edge ([`Edge::weight`])
+/// * Third - weight of the edge ([`Edge::weight`]), parsed to the [`crate::Weight`] type of the graph
+///
+/// If the graph has `labels` option, nodes are described by names instead of indices
+/// (see [`GraphBuilder::add_labelled_edge`]).
+/// If the graph has `disconnected` option, it isn't checked if the graph is connected,
+/// even if [`crate::ValidationPolicy`] requires it.
+///
+/// Weight might be followed by attributes of the edge (`name=value` tokens). Attributes of a node are given
+/// in a node line starting with `node`, followed by the node and its attributes (see [`crate::Attributes`]).
+/// Node lines aren't counted as edges.
+///
+/// Everything after `#` is a comment. Blank lines and lines containing only a comment are skipped,
+/// but they are still counted when the number of invalid line is reported - it's the number of line
+/// in the input (starting from 1), like in every format.
 ///
 /// # Example
 /// ```
 /// use graph::Graph;
 ///
-/// let graph: Graph = "4 3
-///     1 2 100
+/// let graph: Graph = "# road network
+///     4 3
+///     1 2 100 # main road
+///
 ///     2 3 200
 ///     4 1 125"
 ///     .parse()
 ///     .unwrap();
 ///
-/// assert_eq!(graph.nodes_count, 4);
-/// assert_eq!(graph.edges.len(), 3);
-/// assert_eq!(graph.edges[0], "1 2 100".parse().unwrap());
-/// assert_eq!(graph.edges[1], "2 3 200".parse().unwrap());
-/// assert_eq!(graph.edges[2], "4 1 125".parse().unwrap());
+/// assert_eq!(graph.nodes_count(), 4);
+/// assert_eq!(graph.edges().len(), 3);
+/// assert_eq!(graph.edges()[0], "1 2 100".parse().unwrap());
+/// assert_eq!(graph.edges()[1], "2 3 200".parse().unwrap());
+/// assert_eq!(graph.edges()[2], "4 1 125".parse().unwrap());
 ///```
 /// # Arguments
 ///
 /// * `input` - string containing graph data
-fn build_graph_from_string(input: &str) -> Result<Graph> {
-    let mut graph_file_reader = GraphDescriptionReader::new(input);
+fn build_graph_from_string<W: Weight>(input: &str) -> Result<Graph<W>> {
+    build_graph_from_description(input.lines().map(Ok), ReadOptions::default())
+}
+
+// builds a graph from lines in native format
+pub(crate) fn build_graph_from_description<W, L, I>(lines: I, options: ReadOptions) -> Result<Graph<W>>
+where
+    W: Weight,
+    L: AsRef<str>,
+    I: Iterator<Item = Result<L>>,
+{
+    let mut graph_file_reader = GraphDescriptionReader::new(lines);
+
+    let (header, first_edge_line) = graph_file_reader.graph_header(options.optional_header)?;
+    let first_line_no = graph_file_reader.next_line_no() - usize::from(first_edge_line.is_some());
 
-    let graph_parameters = graph_file_reader.graph_parameters()?;
+    let lines = first_edge_line
+        .into_iter()
+        .map(Ok)
+        .chain(graph_file_reader)
+        .enumerate()
+        .map(|(position, line)| line.map(|line| (first_line_no + position, line)));
+
+    let policy = header.policy(options.policy);
+    let builder = |graph_parameters| {
+        GraphBuilder::new(graph_parameters)
+            .with_validation_policy(policy)
+            .with_index_base(options.index_base)
+    };
+
+    if !header.nodes_count_inferred && !header.edges_count_inferred {
+        return build_graph_from_edge_lines(builder(header.parameters), lines);
+    }
+
+    // counts have to be inferred from all the edges before any edge can be added
+    let lines = lines.collect::<Result<Vec<_>>>()?;
+    let graph_parameters = infer_counts::<W, L>(header, &lines, options.index_base)?;
+    build_graph_from_edge_lines(builder(graph_parameters), lines.into_iter().map(Ok))
+}
 
-    let mut graph_builder = GraphBuilder::new(graph_parameters);
+// `lines` are pairs (line number, line), blank lines and comments are skipped
+fn build_graph_from_edge_lines<W, L, I>(mut graph_builder: GraphBuilder<W>, lines: I) -> Result<Graph<W>>
+where
+    W: Weight,
+    L: AsRef<str>,
+    I: Iterator<Item = Result<(usize, L)>>,
+{
+    let labelled = graph_builder.is_labelled();
 
-    for (line_no, maybe_edge) in graph_file_reader.enumerate() {
-        let add_edge = || -> Result<()> { graph_builder.add_edge(maybe_edge?) };
+    for line in lines {
+        let (line_no, line) = line?;
+        let line = strip_comment(line.as_ref());
+        if line.is_empty() {
+            continue;
+        }
 
-        add_edge().map_err(|error| BuildGraphError::ErrorInGraphDescriptionFile {
-            line_no: line_no + 1,
-            error:   Box::new(error),
-        })?;
+        add_line(&mut graph_builder, labelled, line).map_err(|error| in_line(line_no, error))?;
     }
 
     graph_builder.build()
 }
 
-impl FromStr for Graph {
+// counts given as `?` (or both counts, if there is no header) are set to the number of nodes used in edge
+// and node lines and the number of edge lines
+pub(crate) fn infer_counts<W, L>(
+    header: GraphHeader,
+    lines: &[(usize, L)],
+    index_base: IndexBase,
+) -> Result<GraphParameters>
+where
+    W: Weight,
+    L: AsRef<str>,
+{
+    let GraphHeader {
+        mut parameters,
+        nodes_count_inferred,
+        edges_count_inferred,
+        ..
+    } = header;
+
+    let lines = lines
+        .iter()
+        .map(|(line_no, line)| (*line_no, strip_comment(line.as_ref())))
+        .filter(|(_, line)| !line.is_empty());
+
+    if edges_count_inferred {
+        parameters.edges_count = lines.clone().filter(|(_, line)| !is_node_line(line)).count();
+    }
+
+    if nodes_count_inferred {
+        parameters.nodes_count = if parameters.labelled {
+            let mut labels = HashSet::new();
+            for (line_no, line) in lines {
+                if is_node_line(line) {
+                    labels.insert(node_line_node(line));
+                    continue;
+                }
+
+                let edge_description =
+                    EdgeDescription::try_from(split_attributes(line, 3).0).map_err(|error| in_line(line_no, error))?;
+                labels.insert(edge_description.from_index);
+                labels.insert(edge_description.to_index);
+            }
+            u32::try_from(labels.len()).unwrap_or(u32::MAX)
+        } else {
+            let mut max_index = None;
+            for (line_no, line) in lines {
+                let index = if is_node_line(line) {
+                    node_line_index(line).map_err(|error| in_line(line_no, error))?
+                } else {
+                    let edge = split_attributes(line, 3)
+                        .0
+                        .parse::<Edge<W>>()
+                        .map_err(|error| in_line(line_no, error))?;
+                    edge.from_index.max(edge.to_index)
+                };
+                max_index = max_index.max(Some(index));
+            }
+            max_index.map_or(0, |index: u32| index.saturating_add(1) - index_base.first_index())
+        };
+    }
+
+    Ok(parameters)
+}
+
+pub(crate) fn in_line(line_no: usize, error: BuildGraphError) -> BuildGraphError {
+    BuildGraphError::ErrorInGraphDescriptionFile {
+        line_no,
+        error: Box::new(error),
+    }
+}
+
+// `line` is an edge line or a node line in native format without a comment
+pub(crate) fn add_line<W: Weight>(graph_builder: &mut GraphBuilder<W>, labelled: bool, line: &str) -> Result<()> {
+    ParsedLine::parse(line, labelled)?.add_to(graph_builder)
+}
+
+/// Edge line or node line in native format, parsed without adding it to the graph
+///
+/// Parsing doesn't depend on the other lines, so lines can be parsed in any order (or in parallel)
+/// and then added to [`GraphBuilder`] in order of the input.
+pub(crate) enum ParsedLine<'a, W> {
+    Edge(Edge<W>, Attributes),
+    LabelledEdge(&'a str, &'a str, W, Attributes),
+    Node(u32, Attributes),
+    LabelledNode(&'a str, Attributes),
+}
+
+impl<'a, W: Weight> ParsedLine<'a, W> {
+    // `line` is an edge line or a node line in native format without a comment
+    pub(crate) fn parse(line: &'a str, labelled: bool) -> Result<Self> {
+        if is_node_line(line) {
+            let attributes = split_attributes(line, 2).1.parse::<Attributes>()?;
+            return if labelled {
+                Ok(ParsedLine::LabelledNode(node_line_node(line), attributes))
+            } else {
+                Ok(ParsedLine::Node(node_line_index(line)?, attributes))
+            };
+        }
+
+        let (edge_line, attributes) = split_attributes(line, 3);
+        if labelled {
+            let edge_description = EdgeDescription::try_from(edge_line)?;
+            let weight = edge_description.parse_weight()?;
+            Ok(ParsedLine::LabelledEdge(
+                edge_description.from_index,
+                edge_description.to_index,
+                weight,
+                attributes.parse()?,
+            ))
+        } else {
+            let edge = edge_line.parse::<Edge<W>>()?;
+            Ok(ParsedLine::Edge(edge, attributes.parse()?))
+        }
+    }
+
+    pub(crate) fn add_to(self, graph_builder: &mut GraphBuilder<W>) -> Result<()> {
+        match self {
+            ParsedLine::Edge(edge, attributes) => graph_builder.add_edge_with_attributes(edge, attributes),
+            ParsedLine::LabelledEdge(from_label, to_label, weight, attributes) => {
+                graph_builder.add_labelled_edge_with_attributes(from_label, to_label, weight, attributes)
+            }
+            ParsedLine::Node(index, attributes) => graph_builder.set_node_attributes(index, attributes),
+            ParsedLine::LabelledNode(label, attributes) => {
+                graph_builder.set_labelled_node_attributes(label, attributes)
+            }
+        }
+    }
+}
+
+// node described by node line (its second token)
+fn node_line_node(line: &str) -> &str {
+    line.split_whitespace().nth(1).unwrap_or_default()
+}
+
+// index of the node described by node line of graph without labels
+fn node_line_index(line: &str) -> Result<u32> {
+    let node = node_line_node(line);
+    node.parse()
+        .map_err(|_| BuildGraphError::from(AttributeError::NodeIndexValueMustBeInteger(node.to_owned())))
+}
+
+impl<W: Weight> FromStr for Graph<W> {
     type Err = BuildGraphError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -121,30 +614,154 @@ impl FromStr for Graph {
     }
 }
 
-type DataIter<'a> = std::str::Lines<'a>;
+/// Character starting a comment in graph description
+pub(crate) const COMMENT_START: char = '#';
 
-struct GraphDescriptionReader<'a> {
-    iter: DataIter<'a>,
+pub(crate) struct GraphDescriptionReader<I> {
+    iter:       I,
+    // number of lines read so far
+    lines_read: usize,
 }
 
-impl<'a> GraphDescriptionReader<'a> {
-    pub fn new(input: &'a str) -> Self {
-        Self { iter: input.lines() }
+impl<L, I> GraphDescriptionReader<I>
+where
+    L: AsRef<str>,
+    I: Iterator<Item = Result<L>>,
+{
+    pub fn new(iter: I) -> Self {
+        Self { iter, lines_read: 0 }
+    }
+
+    // number of the line in the input, which will be read next (starting from 1)
+    pub fn next_line_no(&self) -> usize {
+        self.lines_read + 1
     }
 
-    pub fn graph_parameters(&mut self) -> Result<GraphParameters> {
-        let line = self
-            .iter
-            .next()
-            .ok_or_else(|| BuildGraphError::from(GraphParametersParsingError::EmptyInput))?;
-        GraphParameters::try_from(line)
+    // first line that isn't blank or a comment contains graph parameters, if the header is optional
+    // and the line isn't valid graph parameters, it's returned as the first edge line
+    // and all counts have to be inferred
+    pub fn graph_header(&mut self, optional: bool) -> Result<(GraphHeader, Option<L>)> {
+        for line in self.by_ref() {
+            let line = line?;
+            let stripped_line = strip_comment(line.as_ref());
+            if stripped_line.is_empty() {
+                continue;
+            }
+
+            return match GraphHeader::try_from(stripped_line) {
+                Ok(header) => Ok((header, None)),
+                Err(_) if optional => Ok((GraphHeader::missing(), Some(line))),
+                Err(error) => Err(error),
+            };
+        }
+
+        Err(BuildGraphError::from(GraphParametersParsingError::EmptyInput))
     }
 }
 
-impl<'a> Iterator for GraphDescriptionReader<'a> {
-    type Item = Result<Edge>;
+// removes comment (starting with `#`) and surrounding whitespaces from the line
+pub(crate) fn strip_comment(line: &str) -> &str {
+    match line.find(COMMENT_START) {
+        Some(position) => line[..position].trim(),
+        None => line.trim(),
+    }
+}
+
+impl<L, I> Iterator for GraphDescriptionReader<I>
+where
+    L: AsRef<str>,
+    I: Iterator<Item = Result<L>>,
+{
+    type Item = Result<L>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next().map(str::parse)
+        let line = self.iter.next()?;
+        self.lines_read += 1;
+        Some(line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParsingEdgeError;
+    use test_case::test_case;
+
+    fn read(input: &str, options: ReadOptions) -> Result<Graph> {
+        build_graph_with_options(input, options)
+    }
+
+    #[test_case("4 ?\n1 2 100\n\n2 3 80 # path\n3 4 50" => (4, 3); "inferred edges count")]
+    #[test_case("? ?\n1 2 100\n2 3 80" => (3, 2); "inferred nodes and edges count")]
+    #[test_case("? ? directed labels\ngate pond 100\npond gate 80" => (2, 2); "inferred labels count")]
+    fn inferred_counts(input: &str) -> (u32, usize) {
+        let graph = read(input, ReadOptions::new()).unwrap();
+        (graph.nodes_count, graph.edges.len())
+    }
+
+    #[test]
+    fn inferred_nodes_count_with_zero_base() -> Result<()> {
+        let graph = read(
+            "? ?\n0 1 100\n1 2 80",
+            ReadOptions::new().with_index_base(IndexBase::Zero),
+        )?;
+
+        assert_eq!(graph.nodes_count, 3);
+        assert_eq!(graph.edges[1], Edge::new(2, 3, 80));
+        Ok(())
+    }
+
+    #[test]
+    fn missing_header() -> Result<()> {
+        let graph = read(
+            "# exported edges\n1 2 100\n2 3 80\n3 1 40",
+            ReadOptions::new().with_optional_header(true),
+        )?;
+
+        assert_eq!(graph.nodes_count, 3);
+        assert_eq!(graph.edges[0], Edge::new(1, 2, 100));
+        assert_eq!(graph.edges.len(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn missing_header_is_an_error_by_default() {
+        let expected = BuildGraphError::from(GraphParametersParsingError::UnknownGraphOption("100".to_owned()));
+        let actual = read("1 2 100\n2 3 80", ReadOptions::new()).unwrap_err();
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn disconnected_option() -> Result<()> {
+        let input = "4 2 disconnected\n1 2 10\n3 4 20";
+        let graph = read(input, ReadOptions::new())?;
+
+        assert_eq!(graph.connected_components().count(), 2);
+        assert_eq!(graph.to_string().parse::<Graph>()?, graph);
+        Ok(())
+    }
+
+    #[test_case("? ?\n1 2 100\n2 x 80", ReadOptions::new(), 3; "with header")]
+    #[test_case("1 2 100\n2 x 80", ReadOptions::new().with_optional_header(true), 2; "without header")]
+    #[test_case("# roads\n\n1 2 100\n2 x 80", ReadOptions::new().with_optional_header(true), 4; "comment without header")]
+    fn invalid_line_with_inferred_counts(input: &str, options: ReadOptions, line_no: usize) {
+        let expected = BuildGraphError::ErrorInGraphDescriptionFile {
+            line_no,
+            error: Box::new(BuildGraphError::from(ParsingEdgeError::ToIndexValueMustBeInteger(
+                "x".to_owned(),
+            ))),
+        };
+        let actual = read(input, options).unwrap_err();
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn inferred_count_in_graph_parameters() {
+        let expected = BuildGraphError::from(GraphParametersParsingError::EdgesCountValueIsNotInteger("?".to_owned()));
+        let actual = GraphParameters::try_from("3 ?").unwrap_err();
+
+        assert_eq!(actual.to_string(), expected.to_string());
     }
 }
@@ -0,0 +1,276 @@
+use crate::labels::node_labels;
+use crate::{Attributes, BuildGraphError, BuildGraphResult, Edge, Graph, GraphBuilder, GraphKind, GraphParameters};
+use crate::{ValidationPolicy, Weight};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+/// Serde representation of [`Graph`] (available with `serde` feature)
+///
+/// [`Graph`] is serialized as this structure and deserialized from it using [`Graph::try_from`],
+/// which adds edges with [`GraphBuilder::add_edge`] and checks the graph with [`GraphBuilder::build`].
+/// Deserializing [`Graph`] directly gives serde error with the message of [`BuildGraphError`],
+/// deserializing [`GraphData`] and converting it gives [`BuildGraphError`] itself.
+///
+/// Connectivity of the graph is checked, unless `disconnected` is set (it's set while serializing graphs
+/// which aren't connected, so every graph can be deserialized back).
+///
+/// # Example
+/// ```
+/// use graph::{BuildGraphError, Graph, GraphData};
+/// use std::convert::TryFrom;
+///
+/// let graph: Graph = serde_json::from_str(r#"{
+///     "nodes_count": 3,
+///     "edges": [
+///         { "from_index": 1, "to_index": 2, "weight": 100 },
+///         { "from_index": 2, "to_index": 3, "weight": 150 }
+///     ]
+/// }"#)
+/// .unwrap();
+///
+/// assert_eq!(graph.edges()[1], "2 3 150".parse().unwrap());
+///
+/// let graph_data: GraphData = serde_json::from_str(r#"{ "nodes_count": 3, "edges": [] }"#).unwrap();
+///
+/// assert!(matches!(Graph::try_from(graph_data), Err(BuildGraphError::GraphNotConnected)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphData<W = i32> {
+    /// Number of nodes in graph (indexed from 1 to `nodes_count`)
+    pub nodes_count: u32,
+
+    /// Kind of the graph (undirected if it's missing)
+    #[serde(default)]
+    pub kind: GraphKind,
+
+    /// Labels of the nodes, label of node with index `i` is at position `i - 1`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub labels: Option<Vec<String>>,
+
+    /// Vector of edges
+    pub edges: Vec<Edge<W>>,
+
+    /// Attributes of the nodes with given indices
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub node_attributes: BTreeMap<u32, Attributes>,
+
+    /// Attributes of the edges at given positions in `edges`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub edge_attributes: BTreeMap<usize, Attributes>,
+
+    /// Tells if graph might be disconnected, so its connectivity isn't checked
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub disconnected: bool,
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+impl<W: Weight> From<Graph<W>> for GraphData<W> {
+    fn from(graph: Graph<W>) -> Self {
+        let labels = graph
+            .labels()
+            .map(|labels| labels.iter().map(|(_, label)| label.to_owned()).collect());
+
+        let node_attributes = (1..=graph.nodes_count)
+            .filter_map(|index| Some((index, graph.node_attributes_at(index)?.clone())))
+            .collect();
+        let edge_attributes = (0..graph.edges.len())
+            .filter_map(|position| Some((position, graph.edge_attributes_at(position)?.clone())))
+            .collect();
+        let disconnected = !graph.is_connected();
+
+        GraphData {
+            nodes_count: graph.nodes_count,
+            kind: graph.kind,
+            labels,
+            edges: graph.edges,
+            node_attributes,
+            edge_attributes,
+            disconnected,
+        }
+    }
+}
+
+impl<W: Weight> TryFrom<GraphData<W>> for Graph<W> {
+    type Error = BuildGraphError;
+
+    fn try_from(graph_data: GraphData<W>) -> BuildGraphResult<Self, Self::Error> {
+        let GraphData {
+            nodes_count,
+            kind,
+            labels,
+            edges,
+            node_attributes,
+            edge_attributes,
+            disconnected,
+        } = graph_data;
+
+        let labels = labels.map(|labels| node_labels(labels, nodes_count)).transpose()?;
+
+        let policy = ValidationPolicy::default().with_connectivity(!disconnected);
+        let mut graph_builder = GraphBuilder::new(GraphParameters::new(nodes_count, edges.len()).with_kind(kind))
+            .with_validation_policy(policy);
+        for edge in edges {
+            graph_builder.add_edge(edge)?;
+        }
+        for (position, attributes) in edge_attributes {
+            graph_builder.set_edge_attributes(position, attributes)?;
+        }
+        for (index, attributes) in node_attributes {
+            graph_builder.set_node_attributes(index, attributes)?;
+        }
+
+        let graph = graph_builder.build()?;
+
+        Ok(match labels {
+            Some(labels) => graph.with_labels(labels),
+            None => graph,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AddingEdgeError, AttributeError, AttributeValue, OrderedFloat};
+
+    #[test]
+    fn json_round_trip() -> BuildGraphResult<()> {
+        let graph: Graph = "3 3 directed labels
+            gate pond 100
+            pond fountain 50
+            fountain gate 70"
+            .parse()?;
+
+        let json = serde_json::to_string(&graph).unwrap();
+        let deserialized: Graph = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(deserialized.nodes_count, graph.nodes_count);
+        assert_eq!(deserialized.kind, graph.kind);
+        assert_eq!(deserialized.labels(), graph.labels());
+        assert_eq!(deserialized.edges, graph.edges);
+
+        Ok(())
+    }
+
+    #[test]
+    fn disconnected_round_trip() -> BuildGraphResult<()> {
+        let mut graph: Graph = "4 3\n1 2 10\n2 3 30\n3 4 20".parse()?;
+        graph.remove_node(graph.node_id(2).unwrap()).unwrap();
+
+        let json = serde_json::to_string(&graph).unwrap();
+        let deserialized: Graph = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(deserialized, graph);
+        assert!(json.contains(r#""disconnected":true"#));
+
+        Ok(())
+    }
+
+    #[test]
+    fn yaml_float_weights() {
+        let graph: Graph<OrderedFloat> = serde_yaml::from_str(
+            "
+nodes_count: 2
+kind: undirected
+edges:
+  - from_index: 1
+    to_index: 2
+    weight: 12.5
+",
+        )
+        .unwrap();
+
+        assert_eq!(graph.edges[0].weight, OrderedFloat(12.5));
+    }
+
+    #[test]
+    fn same_error_as_builder() {
+        let graph_data: GraphData = serde_json::from_str(
+            r#"{
+                "nodes_count": 2,
+                "edges": [{ "from_index": 1, "to_index": 3, "weight": 100 }]
+            }"#,
+        )
+        .unwrap();
+
+        let expected = BuildGraphError::from(AddingEdgeError::WrongToIndex {
+            edge:        "1 3 100".to_owned(),
+            nodes_count: 2,
+        });
+
+        let actual = Graph::try_from(graph_data.clone()).unwrap_err();
+        assert_eq!(actual.to_string(), expected.to_string());
+
+        let serde_error = serde_json::from_value::<Graph>(serde_json::to_value(graph_data).unwrap()).unwrap_err();
+        assert_eq!(serde_error.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn duplicate_node_label() {
+        let graph_data: GraphData = GraphData {
+            nodes_count:     2,
+            kind:            GraphKind::Undirected,
+            labels:          Some(vec!["gate".to_owned(), "gate".to_owned()]),
+            edges:           vec![Edge::new(1, 2, 100)],
+            node_attributes: BTreeMap::new(),
+            edge_attributes: BTreeMap::new(),
+            disconnected:    false,
+        };
+
+        let actual = Graph::try_from(graph_data).unwrap_err();
+        assert_eq!(
+            actual.to_string(),
+            BuildGraphError::DuplicateNodeLabel("gate".to_owned()).to_string()
+        );
+    }
+
+    #[test]
+    fn attributes_round_trip() -> BuildGraphResult<()> {
+        let graph: Graph = "3 2
+            1 2 100 name=Main%20Street lanes=2
+            2 3 80 toll=true
+            node 3 height=12.5"
+            .parse()?;
+
+        let json = serde_json::to_string(&graph).unwrap();
+        let deserialized: Graph = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(deserialized, graph);
+
+        let graph_data = GraphData::from(deserialized);
+        assert_eq!(
+            graph_data.edge_attributes[&0].get("name"),
+            Some(&AttributeValue::from("Main Street"))
+        );
+        assert_eq!(
+            graph_data.node_attributes[&3].get("height"),
+            Some(&AttributeValue::Float(12.5))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn attributes_of_unknown_edge() {
+        let graph_data: GraphData = serde_json::from_str(
+            r#"{
+                "nodes_count": 2,
+                "edges": [{ "from_index": 1, "to_index": 2, "weight": 100 }],
+                "edge_attributes": { "1": { "lanes": 2 } }
+            }"#,
+        )
+        .unwrap();
+
+        let expected = BuildGraphError::from(AttributeError::UnknownEdge {
+            position:    1,
+            edges_count: 1,
+        });
+
+        let actual = Graph::try_from(graph_data).unwrap_err();
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+}
@@ -0,0 +1,241 @@
+use crate::structures::nodes_pair;
+use crate::{Graph, GraphKind, IncrementalComponents, Weight};
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// Structural summary of the graph, computed by [`Graph::stats`]
+///
+/// # Example
+/// ```
+/// use graph::{build_graph_with_options, Graph, ReadOptions, ValidationPolicy};
+///
+/// let graph: Graph = build_graph_with_options("5 5
+///     1 2 13
+///     1 3 1
+///     2 3 1
+///     1 1 11
+///     1 3 2", ReadOptions::new().with_policy(ValidationPolicy::permissive()))
+///     .unwrap();
+///
+/// let stats = graph.stats();
+///
+/// assert_eq!(stats.self_loops_count, 1);
+/// assert_eq!(stats.parallel_edges_count, 1);
+/// assert_eq!(stats.isolated_nodes_count, 2);
+/// assert_eq!(stats.components_count, 3);
+/// assert_eq!(stats.degrees.max, 5);
+/// assert_eq!(stats.weight_range, Some((1, 13)));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphStats<W = i32> {
+    /// Number of nodes in graph
+    pub nodes_count: u32,
+
+    /// Number of edges in graph (including self-loops and parallel edges)
+    pub edges_count: usize,
+
+    /// Number of pairs of nodes connected by an edge divided by the number of all pairs of different nodes
+    /// (ordered pairs if graph is directed), so self-loops and parallel edges don't change it.
+    /// Density of graph with less than 2 nodes is 0.
+    pub density: f64,
+
+    /// Distribution of degrees of the nodes
+    pub degrees: DegreeStats,
+
+    /// Number of edges connecting node with itself
+    pub self_loops_count: usize,
+
+    /// Number of edges connecting the same nodes as an earlier edge (in the same direction, if graph is directed)
+    pub parallel_edges_count: usize,
+
+    /// Number of nodes without any edges
+    pub isolated_nodes_count: u32,
+
+    /// Number of connected components (weakly connected, if graph is directed)
+    pub components_count: u32,
+
+    /// The smallest and the greatest weight of the edges (None if there are no edges)
+    pub weight_range: Option<(W, W)>,
+}
+
+/// Distribution of node degrees
+///
+/// Degree of the node is the number of edge ends at the node, so self-loop adds 2 to the degree.
+/// For directed graph it's the sum of in-degree and out-degree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DegreeStats {
+    /// The smallest degree (0 if there are no nodes)
+    pub min: usize,
+
+    /// The greatest degree (0 if there are no nodes)
+    pub max: usize,
+
+    /// Mean degree (0 if there are no nodes)
+    pub mean: f64,
+
+    /// Number of nodes with degree `d` at position `d`, the last position is the greatest degree
+    pub histogram: Vec<u32>,
+}
+
+impl<W: Weight> Graph<W> {
+    /// Computes [`GraphStats`] of the graph
+    pub fn stats(&self) -> GraphStats<W> {
+        let mut degrees = vec![0; self.nodes_count as usize + 1];
+        let mut connected_pairs = HashSet::new();
+        let mut self_loops_count = 0;
+        let mut parallel_edges_count = 0;
+
+        for edge in &self.edges {
+            degrees[edge.from_index as usize] += 1;
+            degrees[edge.to_index as usize] += 1;
+
+            if edge.from_index == edge.to_index {
+                self_loops_count += 1;
+            } else if !connected_pairs.insert(nodes_pair(edge, self.kind)) {
+                parallel_edges_count += 1;
+            }
+        }
+
+        // nodes are indexed from 1, so value with index 0 is skipped
+        let degrees = &degrees[1..];
+
+        let nodes = self.nodes_count as f64;
+        let all_pairs = match self.kind {
+            GraphKind::Directed => nodes * (nodes - 1.0),
+            GraphKind::Undirected => nodes * (nodes - 1.0) / 2.0,
+        };
+        let density = if self.nodes_count < 2 {
+            0.0
+        } else {
+            connected_pairs.len() as f64 / all_pairs
+        };
+
+        let weights = self.edges.iter().map(|edge| edge.weight);
+
+        GraphStats {
+            nodes_count: self.nodes_count,
+            edges_count: self.edges.len(),
+            density,
+            degrees: DegreeStats::new(degrees),
+            self_loops_count,
+            parallel_edges_count,
+            isolated_nodes_count: degrees.iter().filter(|degree| **degree == 0).count() as u32,
+            components_count: IncrementalComponents::from(self).count(),
+            weight_range: weights.clone().min().zip(weights.max()),
+        }
+    }
+}
+
+impl DegreeStats {
+    fn new(degrees: &[usize]) -> DegreeStats {
+        let max = degrees.iter().copied().max().unwrap_or(0);
+
+        let mut histogram = vec![0; if degrees.is_empty() { 0 } else { max + 1 }];
+        for degree in degrees {
+            histogram[*degree] += 1;
+        }
+
+        DegreeStats {
+            min: degrees.iter().copied().min().unwrap_or(0),
+            max,
+            mean: if degrees.is_empty() {
+                0.0
+            } else {
+                degrees.iter().sum::<usize>() as f64 / degrees.len() as f64
+            },
+            histogram,
+        }
+    }
+}
+
+/// Summary is written in lines `name: value`, degree histogram lists only degrees of some nodes (`degree:count`)
+///
+/// # Example
+/// ```
+/// use graph::Graph;
+///
+/// let graph: Graph = "3 3
+///     1 2 100
+///     2 3 80
+///     1 2 120"
+///     .parse()
+///     .unwrap();
+///
+/// assert_eq!(graph.stats().to_string(), "\
+/// nodes: 3
+/// edges: 3 (0 self-loops, 1 parallel edges)
+/// density: 0.667
+/// degree: min 1, max 3, mean 2.00
+/// degree histogram: 1:1 2:1 3:1
+/// isolated nodes: 0
+/// connected components: 1
+/// weights: 80..120
+/// ");
+/// ```
+impl<W: Weight> Display for GraphStats<W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        writeln!(f, "nodes: {}", self.nodes_count)?;
+        writeln!(
+            f,
+            "edges: {} ({} self-loops, {} parallel edges)",
+            self.edges_count, self.self_loops_count, self.parallel_edges_count
+        )?;
+        writeln!(f, "density: {:.3}", self.density)?;
+        writeln!(
+            f,
+            "degree: min {}, max {}, mean {:.2}",
+            self.degrees.min, self.degrees.max, self.degrees.mean
+        )?;
+
+        write!(f, "degree histogram:")?;
+        for (degree, count) in self.degrees.histogram.iter().enumerate() {
+            if *count > 0 {
+                write!(f, " {}:{}", degree, count)?;
+            }
+        }
+        writeln!(f)?;
+
+        writeln!(f, "isolated nodes: {}", self.isolated_nodes_count)?;
+        writeln!(f, "connected components: {}", self.components_count)?;
+        match self.weight_range {
+            Some((min, max)) => writeln!(f, "weights: {}..{}", min, max),
+            None => writeln!(f, "weights: none"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{build_graph_with_options, ReadOptions, ValidationPolicy};
+
+    fn graph(input: &str) -> Graph {
+        build_graph_with_options(input, ReadOptions::new().with_policy(ValidationPolicy::permissive())).unwrap()
+    }
+
+    #[test]
+    fn directed_graph() {
+        let stats = graph("4 4 directed\n1 2 100\n2 1 80\n2 3 -20\n1 2 50").stats();
+
+        assert_eq!(stats.parallel_edges_count, 1);
+        assert!((stats.density - 3.0 / 12.0).abs() < 1e-9);
+        assert_eq!(stats.degrees.histogram, vec![1, 1, 0, 1, 1]);
+        assert_eq!(stats.components_count, 2);
+        assert_eq!(stats.weight_range, Some((-20, 100)));
+    }
+
+    #[test]
+    fn empty_graph() {
+        let stats = graph("0 0").stats();
+
+        assert_eq!(stats.density, 0.0);
+        assert_eq!(stats.degrees, DegreeStats {
+            min:       0,
+            max:       0,
+            mean:      0.0,
+            histogram: vec![],
+        });
+        assert_eq!(stats.components_count, 0);
+        assert_eq!(stats.weight_range, None);
+    }
+}
@@ -1,11 +1,89 @@
-use super::dfs::dfs;
-use crate::adjacency_list::adjacency_list;
+use crate::adjacency_list::AdjacencyCache;
+use crate::attributes::NODE_LINE_START;
+use crate::editing::IdMap;
+use crate::traversal::reachable;
 use crate::{AddingEdgeError, BuildGraphError, BuildGraphResult, GraphParametersParsingError, ParsingEdgeError};
+use crate::{AdjacencyList, AttributeError, Attributes, EdgeId, EdgesCountPolicy, Neighbours, NodeId, NodeLabels};
+use crate::{ValidationPolicy, Weight};
+use parse_display::{Display, FromStr};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::convert::TryFrom;
+use std::fmt::{Display as FmtDisplay, Formatter, Result as FmtResult};
 use std::str::FromStr;
 
+/// Tells whether edges of the graph are one-way or two-way connections
+///
+/// Graph description file might choose the kind with third value in its first line (`directed` or `undirected`),
+/// if it's missing, graph is undirected.
+///
+/// # Example
+/// ```
+/// use graph::{Graph, GraphKind};
+///
+/// let graph: Graph = "3 3 directed
+///     1 2 100
+///     2 3 100
+///     3 1 100"
+///     .parse()
+///     .unwrap();
+///
+/// assert_eq!(graph.kind(), GraphKind::Directed);
+/// ```
+#[derive(Copy, Clone, Debug, Default, Display, FromStr, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "lowercase"))]
+#[display(style = "lowercase")]
+pub enum GraphKind {
+    /// Edge connects `from_index` with `to_index`, but not the other way round
+    Directed,
+
+    /// Edge connects `from_index` and `to_index` in both directions
+    #[default]
+    Undirected,
+}
+
+/// Index of the first node in the input (graph itself always numbers nodes from 1)
+///
+/// Indices given to [`GraphBuilder::add_edge`] are converted to 1-based indices, so with `Zero` base
+/// edge `0 2 100` connects the first node with the third one.
+///
+/// # Example
+/// ```
+/// use graph::{build_graph_with_options, Graph, IndexBase, ReadOptions};
+///
+/// let graph: Graph = build_graph_with_options("3 2
+///     0 1 100
+///     1 2 80", ReadOptions::new().with_index_base(IndexBase::Zero))
+///     .unwrap();
+///
+/// assert_eq!(graph.edges()[0], "1 2 100".parse().unwrap());
+/// ```
+#[derive(Copy, Clone, Debug, Default, Display, FromStr, PartialEq, Eq)]
+#[display(style = "lowercase")]
+pub enum IndexBase {
+    /// Nodes are numbered from 0
+    Zero,
+
+    /// Nodes are numbered from 1
+    #[default]
+    One,
+}
+
+impl IndexBase {
+    /// Index of the first node
+    pub fn first_index(self) -> u32 {
+        match self {
+            IndexBase::Zero => 0,
+            IndexBase::One => 1,
+        }
+    }
+}
+
 /// Basic element of an directed graph, connects ordered pair of nodes(`from_index`, `to_index`)
 ///
+/// Weight of the edge might be any type implementing [`crate::Weight`] (`i32` by default)
+///
 /// # Example
 /// ```
 /// use graph::Edge;
@@ -17,7 +95,8 @@ use std::str::FromStr;
 /// assert_eq!(edge.weight, 200);
 /// ```
 #[derive(Copy, Clone, Debug, PartialEq)]
-pub struct Edge {
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Edge<W = i32> {
     /// Number of the node, where edge starts
     pub from_index: u32,
 
@@ -25,12 +104,12 @@ pub struct Edge {
     pub to_index: u32,
 
     /// Edge weight
-    pub weight: i32,
+    pub weight: W,
 }
 
-impl Edge {
+impl<W: Weight> Edge<W> {
     /// Edge constructor
-    pub fn new(from_index: u32, to_index: u32, weight: i32) -> Edge {
+    pub fn new(from_index: u32, to_index: u32, weight: W) -> Edge<W> {
         Edge {
             from_index,
             to_index,
@@ -39,7 +118,14 @@ impl Edge {
     }
 }
 
-impl FromStr for Edge {
+// edge is written the same way as in graph description file
+impl<W: Weight> FmtDisplay for Edge<W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{} {} {}", self.from_index, self.to_index, self.weight)
+    }
+}
+
+impl<W: Weight> FromStr for Edge<W> {
     type Err = BuildGraphError;
 
     fn from_str(s: &str) -> BuildGraphResult<Self, Self::Err> {
@@ -49,7 +135,7 @@ impl FromStr for Edge {
 }
 
 /// Struct containing slices, might be converted to [`Edge`]
-/// only if slices are parsable to u32 or i32 (depending on the field)
+/// only if slices are parsable to u32 or weight type (depending on the field)
 #[derive(Debug)]
 pub struct EdgeDescription<'a> {
     /// Number of the node, where edge starts
@@ -77,6 +163,12 @@ impl<'a> TryFrom<&'a str> for EdgeDescription<'a> {
             .next()
             .ok_or_else(|| BuildGraphError::from(ParsingEdgeError::MissingWeightField))?;
 
+        if let Some(token) = iter.next() {
+            return Err(BuildGraphError::from(ParsingEdgeError::UnexpectedTrailingToken(
+                token.to_owned(),
+            )));
+        }
+
         Ok(EdgeDescription {
             from_index,
             to_index,
@@ -85,7 +177,16 @@ impl<'a> TryFrom<&'a str> for EdgeDescription<'a> {
     }
 }
 
-impl<'a> TryFrom<EdgeDescription<'a>> for Edge {
+impl<'a> EdgeDescription<'a> {
+    /// Parses `weight` slice of the edge description
+    pub fn parse_weight<W: Weight>(&self) -> BuildGraphResult<W> {
+        self.weight
+            .parse::<W>()
+            .map_err(|_| BuildGraphError::from(ParsingEdgeError::WeightValueMustBeInteger(self.weight.to_owned())))
+    }
+}
+
+impl<'a, W: Weight> TryFrom<EdgeDescription<'a>> for Edge<W> {
     type Error = BuildGraphError;
 
     fn try_from(edge_description: EdgeDescription<'a>) -> BuildGraphResult<Self, Self::Error> {
@@ -101,17 +202,15 @@ impl<'a> TryFrom<EdgeDescription<'a>> for Edge {
             ))
         })?;
 
-        let parsed_weight = edge_description.weight.parse::<i32>().map_err(|_| {
-            BuildGraphError::from(ParsingEdgeError::WeightValueMustBeInteger(
-                edge_description.weight.to_owned(),
-            ))
-        })?;
+        let parsed_weight = edge_description.parse_weight()?;
 
         Ok(Edge::new(parsed_from_index, parsed_to_index, parsed_weight))
     }
 }
 
-/// Directed graph, containing edges list and number of nodes
+/// Directed or undirected graph (see [`GraphKind`]), containing edges list and number of nodes
+///
+/// Edges weights might be any type implementing [`crate::Weight`] (`i32` by default)
 ///
 /// Could be built using [`crate::build_graph`] or [`GraphBuilder::build`]
 ///
@@ -125,29 +224,320 @@ impl<'a> TryFrom<EdgeDescription<'a>> for Edge {
 ///     .parse()
 ///     .unwrap();
 ///
-/// assert_eq!(graph.nodes_count, 3);
-/// assert_eq!(graph.edges.len(), 2);
-/// assert_eq!(graph.edges[0], "1 3 250".parse().unwrap());
-/// assert_eq!(graph.edges[1], "2 1 120".parse().unwrap());
+/// assert_eq!(graph.nodes_count(), 3);
+/// assert_eq!(graph.edges().len(), 2);
+/// assert_eq!(graph.edges()[0], "1 3 250".parse().unwrap());
+/// assert_eq!(graph.edges()[1], "2 1 120".parse().unwrap());
 /// ```
 #[derive(Debug, Clone)]
-pub struct Graph {
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(
+        try_from = "crate::GraphData<W>",
+        into = "crate::GraphData<W>",
+        bound(serialize = "W: Weight + Serialize", deserialize = "W: Weight + Deserialize<'de>")
+    )
+)]
+pub struct Graph<W = i32> {
     /// Number of nodes in graph (indexed from 1 to `nodes_count`)
-    pub nodes_count: u32,
+    pub(crate) nodes_count: u32,
 
     /// Vector of edges
-    pub edges: Vec<Edge>,
+    pub(crate) edges: Vec<Edge<W>>,
+
+    /// Tells if edges are one-way or two-way connections
+    pub(crate) kind: GraphKind,
+
+    /// Names of the nodes (if graph is labelled)
+    pub(crate) labels: Option<NodeLabels>,
+
+    /// Adjacency structure, built on first use
+    pub(crate) adjacency: AdjacencyCache<W>,
+
+    /// Handles of the nodes (see [`crate::NodeId`])
+    pub(crate) node_ids: IdMap,
+
+    /// Handles of the edges (see [`crate::EdgeId`])
+    pub(crate) edge_ids: IdMap,
+
+    /// Attributes of the nodes (see [`crate::Attributes`])
+    pub(crate) node_attributes: HashMap<NodeId, Attributes>,
+
+    /// Attributes of the edges
+    pub(crate) edge_attributes: HashMap<EdgeId, Attributes>,
 }
 
-impl Graph {
+impl<W: Weight> Graph<W> {
     /// Creates graph from number of nodes and vector of edges
     ///
     /// # Arguments
     ///
     /// * `nodes_count` - number of nodes in the graph
     /// * `edges` - vector of [`crate::Edge`]
-    pub fn new(nodes_count: u32, edges: Vec<Edge>) -> Graph {
-        Graph { nodes_count, edges }
+    /// * `kind` - [`GraphKind`] of the graph
+    pub fn new(nodes_count: u32, edges: Vec<Edge<W>>, kind: GraphKind) -> Graph<W> {
+        Graph {
+            nodes_count,
+            edges,
+            kind,
+            labels: None,
+            adjacency: AdjacencyCache::default(),
+            node_ids: IdMap::default(),
+            edge_ids: IdMap::default(),
+            node_attributes: HashMap::new(),
+            edge_attributes: HashMap::new(),
+        }
+    }
+
+    /// Gives names to the nodes of the graph
+    ///
+    /// # Arguments
+    ///
+    /// * `labels` - [`crate::NodeLabels`] mapping names of the nodes to their indices
+    pub fn with_labels(self, labels: NodeLabels) -> Graph<W> {
+        Graph {
+            labels: Some(labels),
+            ..self
+        }
+    }
+
+    /// Returns number of nodes in graph (indexed from 1 to `nodes_count`)
+    pub fn nodes_count(&self) -> u32 {
+        self.nodes_count
+    }
+
+    /// Returns edges of the graph
+    ///
+    /// Edges are changed only by editing methods (see [`Graph::add_edge`]), which keep handles
+    /// and adjacency structure of the graph up to date.
+    pub fn edges(&self) -> &[Edge<W>] {
+        &self.edges
+    }
+
+    /// Tells if edges are one-way or two-way connections
+    pub fn kind(&self) -> GraphKind {
+        self.kind
+    }
+
+    /// Returns names of the nodes, if graph is labelled
+    pub fn labels(&self) -> Option<&NodeLabels> {
+        self.labels.as_ref()
+    }
+
+    /// Returns name of the node for messages - its label if graph is labelled, otherwise its index
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - index of the node
+    pub fn node_name(&self, index: u32) -> String {
+        match self.labels().and_then(|labels| labels.label(index)) {
+            Some(label) => label.to_owned(),
+            None => index.to_string(),
+        }
+    }
+
+    /// Writes edge the same way as in graph description file (nodes are described by their names,
+    /// see [`Graph::node_name`]), so it could be used in messages
+    ///
+    /// # Example
+    /// ```
+    /// use graph::Graph;
+    ///
+    /// let graph: Graph = "2 1 labels
+    ///     gate pond 120"
+    ///     .parse()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(graph.describe_edge(&graph.edges()[0]), "gate pond 120");
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `edge` - edge of the graph
+    pub fn describe_edge(&self, edge: &Edge<W>) -> String {
+        format!(
+            "{} {} {}",
+            self.node_name(edge.from_index),
+            self.node_name(edge.to_index),
+            edge.weight
+        )
+    }
+
+    /// Returns [`crate::AdjacencyList`] of the graph
+    ///
+    /// It's built when this method (or [`Graph::neighbours`]) is called for the first time
+    /// and then reused by all the algorithms running on the graph, so `edges` shouldn't be changed directly
+    /// after that (editing methods like [`Graph::add_edge`] rebuild it).
+    pub fn adjacency_list(&self) -> &AdjacencyList<W> {
+        self.adjacency
+            .get_or_init(|| AdjacencyList::new(&self.edges, self.nodes_count, self.kind))
+    }
+
+    /// Returns iterator over pairs (`neighbour_index`, `weight`) of node with given index
+    ///
+    /// For undirected graph neighbours are nodes connected with an edge in any direction,
+    /// for directed graph - nodes which edges starting in given node lead to.
+    ///
+    /// # Example
+    /// ```
+    /// use graph::Graph;
+    ///
+    /// let graph: Graph = "3 2
+    ///     1 3 250
+    ///     2 1 120"
+    ///     .parse()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(graph.neighbours(1).collect::<Vec<_>>(), vec![(3, 250), (2, 120)]);
+    /// assert_eq!(graph.neighbours(2).collect::<Vec<_>>(), vec![(1, 120)]);
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - index of the node
+    pub fn neighbours(&self, index: u32) -> Neighbours<'_, W> {
+        self.adjacency_list().neighbours(index)
+    }
+
+    /// Returns the first edge with negative weight (or None if all weights are non-negative)
+    ///
+    /// # Example
+    /// ```
+    /// use graph::Graph;
+    ///
+    /// let graph: Graph = "3 2
+    ///     1 3 250
+    ///     2 1 -120"
+    ///     .parse()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(graph.negative_weight_edge(), Some(&graph.edges()[1]));
+    /// ```
+    pub fn negative_weight_edge(&self) -> Option<&Edge<W>> {
+        self.edges.iter().find(|edge| edge.weight.is_negative())
+    }
+
+    // checks if there is a path from any node to any other node
+    // (for directed graph edges are followed only in their direction, so graph must be strongly connected)
+    pub(crate) fn is_connected(&self) -> bool {
+        if self.nodes_count == 0 {
+            return true;
+        }
+
+        if !all_visited(&reachable(1, self.adjacency_list())) {
+            return false;
+        }
+
+        match self.kind {
+            GraphKind::Undirected => true,
+            // every node must be also able to reach the first node, so search the graph with reversed edges
+            GraphKind::Directed => all_visited(&reachable(1, &AdjacencyList::reversed(&self.edges, self.nodes_count))),
+        }
+    }
+
+    // labels can be written in text format only if reading the edges gives every node the same label,
+    // so nodes must be labelled in order of their first appearance in edges and labels must be single tokens
+    fn text_labels(&self) -> Option<&NodeLabels> {
+        let labels = self.labels()?;
+
+        let mut labelled_count = 0;
+        for index in self.edges.iter().flat_map(|edge| [edge.from_index, edge.to_index]) {
+            if index <= labelled_count {
+                continue;
+            }
+            if index != labelled_count + 1 || !is_text_label(labels.label(index)?) {
+                return None;
+            }
+            labelled_count = index;
+        }
+
+        if labels.len() == labelled_count as usize {
+            Some(labels)
+        } else {
+            None
+        }
+    }
+}
+
+// label is read as a single token, which doesn't start a comment
+fn is_text_label(label: &str) -> bool {
+    !label.is_empty() && !label.contains(|c: char| c.is_whitespace() || c == '#')
+}
+
+/// Graph is written in the format read by [`crate::build_graph`], so parsing the output gives the same graph
+///
+/// Labelled graph is written with `labels` option, unless reading its edges would give the nodes different labels
+/// (labels containing whitespace or `#`, or nodes not labelled in order of their first appearance in edges,
+/// which can happen only for graphs created with [`Graph::with_labels`]). Then the nodes are written as indices.
+/// Attributes of the edges follow their weights, attributes of the nodes are written in node lines after the edges
+/// (see [`crate::Attributes`]). Graph which isn't connected is written with `disconnected` option,
+/// so it can be read with the default [`ValidationPolicy`].
+///
+/// # Example
+/// ```
+/// use graph::Graph;
+///
+/// let graph: Graph = "3 3 directed labels
+///     gate pond 100
+///     pond fountain 50
+///     fountain gate 70"
+///     .parse()
+///     .unwrap();
+///
+/// let description = graph.to_string();
+///
+/// assert_eq!(description, "3 3 directed labels\ngate pond 100\npond fountain 50\nfountain gate 70\n");
+/// assert_eq!(description.parse::<Graph>().unwrap(), graph);
+/// ```
+impl<W: Weight> FmtDisplay for Graph<W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let labels = self.text_labels();
+
+        write!(f, "{} {}", self.nodes_count, self.edges.len())?;
+        if self.kind == GraphKind::Directed {
+            write!(f, " {}", self.kind)?;
+        }
+        if labels.is_some() {
+            write!(f, " {}", LABELS_OPTION)?;
+        }
+        if !self.is_connected() {
+            write!(f, " {}", DISCONNECTED_OPTION)?;
+        }
+        writeln!(f)?;
+
+        // every node in edges has a label, it's checked in `text_labels`
+        let name = |index: u32| match labels {
+            Some(labels) => labels.label(index).unwrap_or_default().to_owned(),
+            None => index.to_string(),
+        };
+
+        for (position, edge) in self.edges.iter().enumerate() {
+            write!(f, "{} {} {}", name(edge.from_index), name(edge.to_index), edge.weight)?;
+            if let Some(attributes) = self.edge_attributes_at(position) {
+                write!(f, " {}", attributes)?;
+            }
+            writeln!(f)?;
+        }
+
+        for index in 1..=self.nodes_count {
+            if let Some(attributes) = self.node_attributes_at(index) {
+                writeln!(f, "{} {} {}", NODE_LINE_START, name(index), attributes)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// cached adjacency list and handles aren't compared, attributes are compared by indices and positions
+impl<W: Weight> PartialEq for Graph<W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.nodes_count == other.nodes_count
+            && self.kind == other.kind
+            && self.labels == other.labels
+            && self.edges == other.edges
+            && self.attributes_eq(other)
     }
 }
 
@@ -157,10 +547,7 @@ impl Graph {
 /// ```
 /// use graph::{GraphParameters, GraphBuilder, Edge};
 ///
-/// let graph_parameters = GraphParameters {
-///     nodes_count: 3,
-///     edges_count: 2,
-/// };
+/// let graph_parameters = GraphParameters::new(3, 2);
 ///
 /// let mut graph_builder = GraphBuilder::new(graph_parameters);
 ///
@@ -172,67 +559,205 @@ impl Graph {
 ///
 /// let graph = graph_builder.build().unwrap();
 ///
-/// assert_eq!(graph.nodes_count, 3);
-/// assert_eq!(graph.edges[0], first_edge);
-/// assert_eq!(graph.edges[1], second_edge);
+/// assert_eq!(graph.nodes_count(), 3);
+/// assert_eq!(graph.edges()[0], first_edge);
+/// assert_eq!(graph.edges()[1], second_edge);
 /// ```
-pub struct GraphBuilder {
+pub struct GraphBuilder<W = i32> {
     /// Number of nodes in graph
     nodes_count: u32,
 
+    /// Kind of the graph (directed or undirected)
+    kind: GraphKind,
+
+    /// Names of the nodes (if graph is labelled)
+    labels: Option<NodeLabels>,
+
     /// Max number of edges in graph
     max_edges_count: usize,
 
+    /// Tells if edges with negative weight can be added
+    negative_weights_allowed: bool,
+
+    /// Checks done while adding edges and building the graph
+    policy: ValidationPolicy,
+
+    /// Index of the first node in edges given to the builder
+    index_base: IndexBase,
+
+    /// Pairs of nodes connected by added edges, collected only if parallel edges aren't allowed
+    connected_pairs: HashSet<(u32, u32)>,
+
     /// Vector of edges
-    edges: Vec<Edge>,
+    edges: Vec<Edge<W>>,
+
+    /// Attributes of the nodes with given (1-based) indices
+    node_attributes: BTreeMap<u32, Attributes>,
+
+    /// Attributes of the edges at given positions
+    edge_attributes: BTreeMap<usize, Attributes>,
 }
 
-impl GraphBuilder {
+impl<W: Weight> GraphBuilder<W> {
     /// Creates empty graph builder using [`GraphParameters`]
     ///
     /// # Arguments
     ///
-    /// * `gp` - [`crate::GraphParameters`] containing number of nodes and edges in the graph
-    pub fn new(gp: GraphParameters) -> GraphBuilder {
+    /// * `gp` - [`crate::GraphParameters`] containing number of nodes and edges in the graph and its kind
+    pub fn new(gp: GraphParameters) -> GraphBuilder<W> {
         let GraphParameters {
             nodes_count,
             edges_count: max_edges_count,
+            kind,
+            labelled,
         } = gp;
 
         GraphBuilder {
             nodes_count,
+            kind,
+            labels: if labelled { Some(NodeLabels::new()) } else { None },
             max_edges_count,
+            negative_weights_allowed: true,
+            policy: ValidationPolicy::default(),
+            index_base: IndexBase::default(),
+            connected_pairs: HashSet::new(),
             edges: Vec::with_capacity(max_edges_count),
+            node_attributes: BTreeMap::new(),
+            edge_attributes: BTreeMap::new(),
+        }
+    }
+
+    /// Sets checks done while adding edges and building the graph (see [`ValidationPolicy`])
+    ///
+    /// # Example
+    /// ```
+    /// use graph::{GraphBuilder, GraphParameters, Edge, ValidationPolicy};
+    ///
+    /// let mut graph_builder = GraphBuilder::new(GraphParameters::new(3, 2))
+    ///     .with_validation_policy(ValidationPolicy::permissive().with_self_loops(false));
+    ///
+    /// assert!(graph_builder.add_edge(Edge::new(2, 2, 10)).is_err());
+    /// assert!(graph_builder.add_edge(Edge::new(1, 2, 10)).is_ok());
+    ///
+    /// // node 3 isn't connected and there is one edge less than declared
+    /// assert!(graph_builder.build().is_ok());
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - checks done by the builder
+    pub fn with_validation_policy(self, policy: ValidationPolicy) -> GraphBuilder<W> {
+        let kind = self.kind;
+        let connected_pairs = if policy.allow_parallel_edges {
+            HashSet::new()
+        } else {
+            self.edges.iter().map(|edge| nodes_pair(edge, kind)).collect()
+        };
+
+        GraphBuilder {
+            policy,
+            connected_pairs,
+            ..self
+        }
+    }
+
+    /// Sets index of the first node in edges added with [`GraphBuilder::add_edge`] (1 by default)
+    ///
+    /// # Example
+    /// ```
+    /// use graph::{GraphBuilder, GraphParameters, Edge, IndexBase};
+    ///
+    /// let mut graph_builder = GraphBuilder::new(GraphParameters::new(2, 1)).with_index_base(IndexBase::Zero);
+    ///
+    /// assert!(graph_builder.add_edge(Edge::new(0, 2, 10)).is_err());
+    /// assert!(graph_builder.add_edge(Edge::new(0, 1, 10)).is_ok());
+    ///
+    /// assert_eq!(graph_builder.build().unwrap().edges()[0], Edge::new(1, 2, 10));
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `index_base` - index of the first node
+    pub fn with_index_base(self, index_base: IndexBase) -> GraphBuilder<W> {
+        GraphBuilder { index_base, ..self }
+    }
+
+    /// Makes builder refuse edges with negative weight
+    ///
+    /// Negative weights are allowed by default, because some algorithms (e.g. Kruskal's) handle them fine.
+    ///
+    /// # Example
+    /// ```
+    /// use graph::{GraphBuilder, GraphParameters, Edge};
+    ///
+    /// let mut graph_builder = GraphBuilder::new(GraphParameters::new(2, 1)).without_negative_weights();
+    ///
+    /// assert!(graph_builder.add_edge(Edge::new(1, 2, -5)).is_err());
+    /// assert!(graph_builder.add_edge(Edge::new(1, 2, 5)).is_ok());
+    /// ```
+    pub fn without_negative_weights(self) -> GraphBuilder<W> {
+        GraphBuilder {
+            negative_weights_allowed: false,
+            ..self
         }
     }
 
     /// Adds edge to the graph
     ///
-    /// Returns empty result or [`crate::BuildGraphError`] if GraphBuilder is full or
-    /// one of the indices is invalid
+    /// Returns empty result or [`crate::BuildGraphError`] if GraphBuilder is full,
+    /// one of the indices is invalid (0 isn't a valid index unless [`GraphBuilder::with_index_base`] says so),
+    /// weight is negative (see [`GraphBuilder::without_negative_weights`])
+    /// or edge is a self-loop or a parallel edge, which isn't allowed by [`ValidationPolicy`]
     ///
     /// # Arguments
     ///
     /// * `edge` - edge that will be added to the builder
-    pub fn add_edge(&mut self, edge: Edge) -> BuildGraphResult<()> {
+    pub fn add_edge(&mut self, edge: Edge<W>) -> BuildGraphResult<()> {
         if self.edges.len() >= self.max_edges_count {
             return Err(BuildGraphError::from(AddingEdgeError::TooManyEdges {
                 edges_count: self.edges.len(),
-                edge,
+                edge:        self.describe_edge(&edge),
             }));
         }
 
-        if edge.from_index > self.nodes_count {
-            return Err(BuildGraphError::from(AddingEdgeError::WrongFromIndex {
-                edge,
-                nodes_count: self.nodes_count,
+        if self.index_base == IndexBase::One && (edge.from_index == 0 || edge.to_index == 0) {
+            return Err(BuildGraphError::from(AddingEdgeError::ZeroIndex {
+                edge: self.describe_edge(&edge),
             }));
         }
 
-        if edge.to_index > self.nodes_count {
-            return Err(BuildGraphError::from(AddingEdgeError::WrongToIndex {
-                edge,
+        let from_index = self.internal_index(edge.from_index).ok_or_else(|| {
+            BuildGraphError::from(AddingEdgeError::WrongFromIndex {
+                edge:        self.describe_edge(&edge),
                 nodes_count: self.nodes_count,
+            })
+        })?;
+
+        let to_index = self.internal_index(edge.to_index).ok_or_else(|| {
+            BuildGraphError::from(AddingEdgeError::WrongToIndex {
+                edge:        self.describe_edge(&edge),
+                nodes_count: self.nodes_count,
+            })
+        })?;
+
+        if !self.negative_weights_allowed && edge.weight.is_negative() {
+            return Err(BuildGraphError::from(AddingEdgeError::NegativeWeight {
+                edge: self.describe_edge(&edge),
+            }));
+        }
+
+        if !self.policy.allow_self_loops && edge.from_index == edge.to_index {
+            return Err(BuildGraphError::from(AddingEdgeError::SelfLoop {
+                edge: self.describe_edge(&edge),
+            }));
+        }
+
+        let description = edge;
+        let edge = Edge::new(from_index, to_index, edge.weight);
+
+        if !self.policy.allow_parallel_edges && !self.connected_pairs.insert(nodes_pair(&edge, self.kind)) {
+            return Err(BuildGraphError::from(AddingEdgeError::ParallelEdge {
+                edge: self.describe_edge(&description),
             }));
         }
 
@@ -240,64 +765,277 @@ impl GraphBuilder {
         Ok(())
     }
 
-    // checks if there is a path from any node to any other node
-    fn is_connected(&self) -> bool {
-        let adjacency_list = adjacency_list(&self.edges, self.nodes_count);
+    // converts index given in edge to 1-based index used in graph, None if there is no such node
+    fn internal_index(&self, index: u32) -> Option<u32> {
+        match self.index_base {
+            IndexBase::Zero => index.checked_add(1),
+            IndexBase::One => Some(index),
+        }
+        .filter(|index| *index <= self.nodes_count)
+    }
 
-        let visited = dfs(1, &adjacency_list);
+    /// Adds edge connecting nodes with given labels to the graph
+    ///
+    /// Label that wasn't used before gets the next free node index.
+    /// Returns [`crate::BuildGraphError`] if graph isn't labelled, all nodes already have labels
+    /// or edge can't be added (see [`GraphBuilder::add_edge`])
+    ///
+    /// # Example
+    /// ```
+    /// use graph::{GraphBuilder, GraphParameters};
+    ///
+    /// let mut graph_builder = GraphBuilder::new(GraphParameters::new(3, 2).with_labels());
+    ///
+    /// graph_builder.add_labelled_edge("gate", "pond_north", 120).unwrap();
+    /// graph_builder.add_labelled_edge("fountain", "gate", 80).unwrap();
+    ///
+    /// let graph = graph_builder.build().unwrap();
+    ///
+    /// assert_eq!(graph.edges()[1], "3 1 80".parse().unwrap());
+    /// assert_eq!(graph.labels().unwrap().label(3), Some("fountain"));
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `from_label` - name of the node, where edge starts
+    /// * `to_label` - name of the node, where edge ends
+    /// * `weight` - edge weight
+    pub fn add_labelled_edge(&mut self, from_label: &str, to_label: &str, weight: W) -> BuildGraphResult<()> {
+        let describe = || format!("{} {} {}", from_label, to_label, weight);
+
+        let from_index = self.label_index(from_label, &describe)?;
+        let to_index = self.label_index(to_label, &describe)?;
+
+        let first_index = self.index_base.first_index();
+        self.add_edge(Edge::new(
+            from_index - 1 + first_index,
+            to_index - 1 + first_index,
+            weight,
+        ))
+    }
 
-        for value in visited.iter().skip(1) {
-            if !value {
-                return false;
-            }
+    /// Adds edge with attributes to the graph (see [`GraphBuilder::add_edge`])
+    ///
+    /// # Example
+    /// ```
+    /// use graph::{Attributes, Edge, GraphBuilder, GraphParameters};
+    ///
+    /// let mut graph_builder = GraphBuilder::new(GraphParameters::new(2, 1));
+    ///
+    /// let attributes: Attributes = "lanes=2".parse().unwrap();
+    /// graph_builder.add_edge_with_attributes(Edge::new(1, 2, 100), attributes).unwrap();
+    ///
+    /// let graph = graph_builder.build().unwrap();
+    ///
+    /// assert_eq!(graph.to_string(), "2 1\n1 2 100 lanes=2\n");
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `edge` - edge that will be added to the builder
+    /// * `attributes` - attributes of the edge
+    pub fn add_edge_with_attributes(&mut self, edge: Edge<W>, attributes: Attributes) -> BuildGraphResult<()> {
+        self.add_edge(edge)?;
+        self.set_edge_attributes(self.edges.len() - 1, attributes)
+    }
+
+    /// Adds edge with attributes connecting nodes with given labels (see [`GraphBuilder::add_labelled_edge`])
+    ///
+    /// # Arguments
+    ///
+    /// * `from_label` - name of the node, where edge starts
+    /// * `to_label` - name of the node, where edge ends
+    /// * `weight` - edge weight
+    /// * `attributes` - attributes of the edge
+    pub fn add_labelled_edge_with_attributes(
+        &mut self,
+        from_label: &str,
+        to_label: &str,
+        weight: W,
+        attributes: Attributes,
+    ) -> BuildGraphResult<()> {
+        self.add_labelled_edge(from_label, to_label, weight)?;
+        self.set_edge_attributes(self.edges.len() - 1, attributes)
+    }
+
+    /// Adds attributes to the node, replacing values of attributes it already has
+    ///
+    /// Returns [`crate::AttributeError::UnknownNode`] if there is no such node
+    /// (index is given the same way as in edges, see [`GraphBuilder::with_index_base`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - index of the node
+    /// * `attributes` - added attributes
+    pub fn set_node_attributes(&mut self, index: u32, attributes: Attributes) -> BuildGraphResult<()> {
+        let internal_index = self
+            .internal_index(index)
+            .filter(|_| self.index_base == IndexBase::Zero || index != 0)
+            .ok_or_else(|| {
+                BuildGraphError::from(AttributeError::UnknownNode {
+                    node:        index.to_string(),
+                    nodes_count: self.nodes_count,
+                })
+            })?;
+
+        self.node_attributes
+            .entry(internal_index)
+            .or_default()
+            .extend(attributes);
+        Ok(())
+    }
+
+    /// Adds attributes to the node with given label, replacing values of attributes it already has
+    ///
+    /// Label that wasn't used before gets the next free node index (see [`GraphBuilder::add_labelled_edge`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - name of the node
+    /// * `attributes` - added attributes
+    pub fn set_labelled_node_attributes(&mut self, label: &str, attributes: Attributes) -> BuildGraphResult<()> {
+        let index = self.label_index(label, &|| format!("{} {}", NODE_LINE_START, label))?;
+
+        self.node_attributes.entry(index).or_default().extend(attributes);
+        Ok(())
+    }
+
+    // attributes of the edge at given position, empty attributes aren't stored
+    pub(crate) fn set_edge_attributes(&mut self, position: usize, attributes: Attributes) -> BuildGraphResult<()> {
+        if position >= self.edges.len() {
+            return Err(BuildGraphError::from(AttributeError::UnknownEdge {
+                position,
+                edges_count: self.edges.len(),
+            }));
+        }
+
+        if !attributes.is_empty() {
+            self.edge_attributes.insert(position, attributes);
+        }
+        Ok(())
+    }
+
+    // index of the node with given label, label that wasn't used before gets the next free index
+    // (`describe` writes the line containing the label for error messages)
+    fn label_index(&mut self, label: &str, describe: &dyn Fn() -> String) -> BuildGraphResult<u32> {
+        let nodes_count = self.nodes_count;
+        let labels = self
+            .labels
+            .as_mut()
+            .ok_or_else(|| BuildGraphError::from(AddingEdgeError::NodeLabelsNotDeclared { edge: describe() }))?;
+
+        match labels.index(label) {
+            Some(index) => Ok(index),
+            None if labels.len() < nodes_count as usize => Ok(labels.insert(label)),
+            None => Err(BuildGraphError::from(AddingEdgeError::TooManyNodeLabels {
+                edge: describe(),
+                label: label.to_owned(),
+                nodes_count,
+            })),
         }
-        true
+    }
+
+    // writes edge the same way as in graph description file, so it could be used in error messages
+    fn describe_edge(&self, edge: &Edge<W>) -> String {
+        let label = |index: u32| self.labels.as_ref().zip(self.internal_index(index));
+        let name = |index: u32| match label(index).and_then(|(labels, index)| labels.label(index)) {
+            Some(label) => label.to_owned(),
+            None => index.to_string(),
+        };
+
+        format!("{} {} {}", name(edge.from_index), name(edge.to_index), edge.weight)
+    }
+
+    // tells if edges should be added with [`GraphBuilder::add_labelled_edge`]
+    pub(crate) fn is_labelled(&self) -> bool {
+        self.labels.is_some()
     }
 
     /// Builds [`Graph`] from GraphBuilder
     ///
     /// Returns [`Graph`] wrapped in result or wrapped [`crate::BuildGraphError`] if builder contains less edges than
-    /// declared or graph isn't connected
-    pub fn build(self) -> BuildGraphResult<Graph> {
-        if self.edges.len() < self.max_edges_count {
+    /// declared or graph isn't connected (unless [`ValidationPolicy`] allows it)
+    pub fn build(self) -> BuildGraphResult<Graph<W>> {
+        if self.policy.edges_count == EdgesCountPolicy::Exact && self.edges.len() < self.max_edges_count {
             return Err(BuildGraphError::TooFewEdges {
                 current_count: self.edges.len(),
                 declared:      self.max_edges_count,
             });
         }
 
-        if !self.is_connected() {
+        // adjacency list built while checking connectivity stays in the graph
+        let mut graph = Graph::new(self.nodes_count, self.edges, self.kind);
+        if let Some(labels) = self.labels {
+            graph = graph.with_labels(labels);
+        }
+
+        // handles of the nodes and edges are their indices and positions until the graph is edited
+        graph.node_attributes = self
+            .node_attributes
+            .into_iter()
+            .map(|(index, attributes)| (NodeId(index), attributes))
+            .collect();
+        graph.edge_attributes = self
+            .edge_attributes
+            .into_iter()
+            .map(|(position, attributes)| (EdgeId(position), attributes))
+            .collect();
+
+        if self.policy.require_connected && !graph.is_connected() {
             return Err(BuildGraphError::GraphNotConnected);
         }
 
-        Ok(Graph::new(self.nodes_count, self.edges))
+        Ok(graph)
     }
 }
 
+// edges of undirected graph connect unordered pairs of nodes
+pub(crate) fn nodes_pair<W>(edge: &Edge<W>, kind: GraphKind) -> (u32, u32) {
+    match kind {
+        GraphKind::Directed => (edge.from_index, edge.to_index),
+        GraphKind::Undirected => (edge.from_index.min(edge.to_index), edge.from_index.max(edge.to_index)),
+    }
+}
+
+// nodes are indexed from 1, so value with index 0 is skipped
+fn all_visited(visited: &[bool]) -> bool {
+    visited.iter().skip(1).all(|value| *value)
+}
+
 // -----------------------------------------------------------------------------
 
 /// Number of nodes and edges in the graph
 ///
 /// # Example
 /// ```
-/// use graph::GraphParameters;
+/// use graph::{GraphKind, GraphParameters};
 ///
 /// let graph_parameters = GraphParameters::new(4, 3);
 ///
 /// assert_eq!(graph_parameters.nodes_count, 4);
 /// assert_eq!(graph_parameters.edges_count, 3);
+/// assert_eq!(graph_parameters.kind, GraphKind::Undirected);
 /// ```
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct GraphParameters {
     /// Number of nodes in the graph (indexed from 1 to `nodes_count`)
     pub nodes_count: u32,
 
     /// Number of edges in the graph
     pub edges_count: usize,
+
+    /// Kind of the graph
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub kind: GraphKind,
+
+    /// Tells if nodes are described by labels instead of indices
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub labelled: bool,
 }
 
 impl GraphParameters {
-    /// Creates GraphParameters from two integers
+    /// Creates GraphParameters of undirected graph without node labels from two integers
     ///
     /// # Arguments
     ///
@@ -307,35 +1045,149 @@ impl GraphParameters {
         GraphParameters {
             nodes_count,
             edges_count: max_edges_count,
+            kind: GraphKind::default(),
+            labelled: false,
         }
     }
+
+    /// Changes kind of the graph described by the parameters
+    ///
+    /// # Example
+    /// ```
+    /// use graph::{GraphKind, GraphParameters};
+    ///
+    /// let graph_parameters = GraphParameters::new(4, 3).with_kind(GraphKind::Directed);
+    ///
+    /// assert_eq!(graph_parameters.kind, GraphKind::Directed);
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - [`GraphKind`] of the graph
+    pub fn with_kind(self, kind: GraphKind) -> GraphParameters {
+        GraphParameters { kind, ..self }
+    }
+
+    /// Makes nodes of the graph described by labels (see [`GraphBuilder::add_labelled_edge`])
+    pub fn with_labels(self) -> GraphParameters {
+        GraphParameters { labelled: true, ..self }
+    }
 }
 
+/// Graph option telling that nodes are described by labels
+const LABELS_OPTION: &str = "labels";
+
+/// Graph option telling that graph doesn't have to be connected
+const DISCONNECTED_OPTION: &str = "disconnected";
+
+/// Value of nodes or edges count telling that it should be inferred from the edges
+const INFERRED_COUNT: &str = "?";
+
 impl TryFrom<&str> for GraphParameters {
     type Error = BuildGraphError;
 
+    fn try_from(line: &str) -> BuildGraphResult<Self, Self::Error> {
+        let header = GraphHeader::try_from(line)?;
+
+        if header.nodes_count_inferred {
+            return Err(BuildGraphError::from(
+                GraphParametersParsingError::NodesCountValueMustBeInteger(INFERRED_COUNT.to_owned()),
+            ));
+        }
+
+        if header.edges_count_inferred {
+            return Err(BuildGraphError::from(
+                GraphParametersParsingError::EdgesCountValueIsNotInteger(INFERRED_COUNT.to_owned()),
+            ));
+        }
+
+        Ok(header.parameters)
+    }
+}
+
+// first line of graph description, counts given as `?` are set to 0 and have to be inferred from the edges
+pub(crate) struct GraphHeader {
+    pub parameters:           GraphParameters,
+    pub nodes_count_inferred: bool,
+    pub edges_count_inferred: bool,
+    pub disconnected:         bool,
+}
+
+impl GraphHeader {
+    // header of graph description without the first line, both counts are inferred
+    pub fn missing() -> GraphHeader {
+        GraphHeader {
+            parameters:           GraphParameters::new(0, 0),
+            nodes_count_inferred: true,
+            edges_count_inferred: true,
+            disconnected:         false,
+        }
+    }
+
+    // connectivity isn't checked, if the header tells that graph might be disconnected
+    pub fn policy(&self, policy: ValidationPolicy) -> ValidationPolicy {
+        policy.with_connectivity(policy.require_connected && !self.disconnected)
+    }
+}
+
+impl TryFrom<&str> for GraphHeader {
+    type Error = BuildGraphError;
+
     fn try_from(line: &str) -> BuildGraphResult<Self, Self::Error> {
         let mut inner_iter = line.split_whitespace();
 
         let nodes_count = inner_iter.next().unwrap(); // cannot fail !
 
-        let nodes_count = nodes_count.parse::<u32>().map_err(|_| {
-            BuildGraphError::from(GraphParametersParsingError::NodesCountValueMustBeInteger(
-                nodes_count.to_owned(),
-            ))
-        })?;
+        let nodes_count_inferred = nodes_count == INFERRED_COUNT;
+        let nodes_count = match nodes_count {
+            INFERRED_COUNT => 0,
+            _ => nodes_count.parse::<u32>().map_err(|_| {
+                BuildGraphError::from(GraphParametersParsingError::NodesCountValueMustBeInteger(
+                    nodes_count.to_owned(),
+                ))
+            })?,
+        };
 
         let edges_count = inner_iter
             .next()
             .ok_or_else(|| BuildGraphError::from(GraphParametersParsingError::MissingEdgesCountValue))?;
 
-        let edges_count = edges_count.parse::<usize>().map_err(|_| {
-            BuildGraphError::from(GraphParametersParsingError::EdgesCountValueIsNotInteger(
-                edges_count.to_owned(),
-            ))
-        })?;
+        let edges_count_inferred = edges_count == INFERRED_COUNT;
+        let edges_count = match edges_count {
+            INFERRED_COUNT => 0,
+            _ => edges_count.parse::<usize>().map_err(|_| {
+                BuildGraphError::from(GraphParametersParsingError::EdgesCountValueIsNotInteger(
+                    edges_count.to_owned(),
+                ))
+            })?,
+        };
+
+        let mut graph_parameters = GraphParameters::new(nodes_count, edges_count);
+        let mut disconnected = false;
+
+        // the rest of the line contains graph options
+        for option in inner_iter {
+            graph_parameters = match option.parse::<GraphKind>() {
+                Ok(kind) => graph_parameters.with_kind(kind),
+                Err(_) if option == LABELS_OPTION => graph_parameters.with_labels(),
+                Err(_) if option == DISCONNECTED_OPTION => {
+                    disconnected = true;
+                    graph_parameters
+                }
+                Err(_) => {
+                    return Err(BuildGraphError::from(GraphParametersParsingError::UnknownGraphOption(
+                        option.to_owned(),
+                    )))
+                }
+            };
+        }
 
-        Ok(GraphParameters::new(nodes_count, edges_count))
+        Ok(GraphHeader {
+            parameters: graph_parameters,
+            nodes_count_inferred,
+            edges_count_inferred,
+            disconnected,
+        })
     }
 }
 
@@ -360,13 +1212,14 @@ mod tests {
         #[test_case( "", ParsingEdgeError::EmptyLine; "empty line")]
         #[test_case( "1", ParsingEdgeError::MissingToIndexField; "missing to_index field" )]
         #[test_case( "1 2", ParsingEdgeError::MissingWeightField; "missing weight field" )]
+        #[test_case( "1 2 30 40", ParsingEdgeError::UnexpectedTrailingToken("40".to_owned()); "trailing token" )]
         fn fails_because_of_invalid_edge_description(input: &str, expected_error: ParsingEdgeError) {
-            let match_expected = match EdgeDescription::try_from(input).unwrap_err() {
-                BuildGraphError::ParsingEdgeError(actual_err) if actual_err == expected_error => true,
-                _ => false,
-            };
+            let match_expected = matches!(
+                EdgeDescription::try_from(input).unwrap_err(),
+                BuildGraphError::ParsingEdgeError(actual_err) if actual_err == expected_error
+            );
 
-            assert_eq!(match_expected, true);
+            assert!(match_expected);
         }
 
         #[test_case(
@@ -387,7 +1240,7 @@ mod tests {
         fn fails_because_of_non_integer_value(line: &str, expected_error: BuildGraphError) {
             let edge_description = EdgeDescription::try_from(line).unwrap();
 
-            let actual_error = Edge::try_from(edge_description).unwrap_err();
+            let actual_error = Edge::<i32>::try_from(edge_description).unwrap_err();
             assert_eq!(actual_error.to_string(), expected_error.to_string());
         }
     }
@@ -399,6 +1252,8 @@ mod tests {
     const TEST_GRAPH_PARAMETERS: GraphParameters = GraphParameters {
         nodes_count: 3,
         edges_count: 2,
+        kind:        GraphKind::Undirected,
+        labelled:    false,
     };
 
     fn create_test_graph_builder() -> GraphBuilder {
@@ -409,6 +1264,7 @@ mod tests {
 
     mod add_edge {
         use super::*;
+        use test_case::test_case;
 
         #[test]
         fn too_many_edges() -> BuildGraphResult<()> {
@@ -416,11 +1272,11 @@ mod tests {
             graph_builder.add_edge("1 3 200".parse()?)?;
             graph_builder.add_edge("2 1 50".parse()?)?;
 
-            let third_edge = "3 4 170".parse()?;
+            let third_edge: Edge = "3 4 170".parse()?;
 
             let expected = BuildGraphError::from(AddingEdgeError::TooManyEdges {
                 edges_count: TEST_GRAPH_PARAMETERS.edges_count,
-                edge:        third_edge,
+                edge:        third_edge.to_string(),
             });
 
             let actual = graph_builder.add_edge(third_edge).unwrap_err();
@@ -432,10 +1288,10 @@ mod tests {
         #[test]
         fn invalid_from_index() {
             let mut graph_builder = create_test_graph_builder();
-            let invalid_edge = "10 3 120".parse().unwrap();
+            let invalid_edge: Edge = "10 3 120".parse().unwrap();
 
             let expected = BuildGraphError::from(AddingEdgeError::WrongFromIndex {
-                edge:        invalid_edge,
+                edge:        invalid_edge.to_string(),
                 nodes_count: TEST_GRAPH_PARAMETERS.nodes_count,
             });
 
@@ -446,16 +1302,192 @@ mod tests {
         #[test]
         fn invalid_to_index() {
             let mut graph_builder = create_test_graph_builder();
-            let invalid_edge = "2 7 120".parse().unwrap();
+            let invalid_edge: Edge = "2 7 120".parse().unwrap();
+
+            let expected = BuildGraphError::from(AddingEdgeError::WrongToIndex {
+                edge:        invalid_edge.to_string(),
+                nodes_count: TEST_GRAPH_PARAMETERS.nodes_count,
+            });
+
+            let actual = graph_builder.add_edge(invalid_edge).unwrap_err();
+            assert_eq!(actual.to_string(), expected.to_string());
+        }
+
+        #[test]
+        fn negative_weight_allowed() -> BuildGraphResult<()> {
+            let mut graph_builder = create_test_graph_builder();
+            graph_builder.add_edge("1 2 -50".parse()?)?;
+
+            Ok(())
+        }
+
+        #[test]
+        fn negative_weight_not_allowed() {
+            let mut graph_builder = create_test_graph_builder().without_negative_weights();
+            let invalid_edge: Edge = "1 2 -50".parse().unwrap();
+
+            let expected = BuildGraphError::from(AddingEdgeError::NegativeWeight {
+                edge: invalid_edge.to_string(),
+            });
+
+            let actual = graph_builder.add_edge(invalid_edge).unwrap_err();
+            assert_eq!(actual.to_string(), expected.to_string());
+        }
+
+        #[test_case("0 3 120"; "zero from index")]
+        #[test_case("2 0 120"; "zero to index")]
+        fn zero_index(invalid_edge: &str) {
+            let mut graph_builder = create_test_graph_builder();
+            let invalid_edge: Edge = invalid_edge.parse().unwrap();
+
+            let expected = BuildGraphError::from(AddingEdgeError::ZeroIndex {
+                edge: invalid_edge.to_string(),
+            });
+
+            let actual = graph_builder.add_edge(invalid_edge).unwrap_err();
+            assert_eq!(actual.to_string(), expected.to_string());
+        }
+
+        #[test]
+        fn zero_based_indices() -> BuildGraphResult<()> {
+            let mut graph_builder = create_test_graph_builder().with_index_base(IndexBase::Zero);
+            graph_builder.add_edge("0 2 200".parse()?)?;
 
+            let invalid_edge: Edge = "1 3 50".parse()?;
             let expected = BuildGraphError::from(AddingEdgeError::WrongToIndex {
-                edge:        invalid_edge,
+                edge:        invalid_edge.to_string(),
                 nodes_count: TEST_GRAPH_PARAMETERS.nodes_count,
             });
+            let actual = graph_builder.add_edge(invalid_edge).unwrap_err();
+            assert_eq!(actual.to_string(), expected.to_string());
+
+            graph_builder.add_edge("1 0 50".parse()?)?;
+
+            assert_eq!(graph_builder.build()?.edges, vec![
+                Edge::new(1, 3, 200),
+                Edge::new(2, 1, 50)
+            ]);
+            Ok(())
+        }
+
+        #[test]
+        fn zero_based_labelled_edges() -> BuildGraphResult<()> {
+            let mut graph_builder: GraphBuilder =
+                GraphBuilder::new(GraphParameters::new(2, 1).with_labels()).with_index_base(IndexBase::Zero);
+            graph_builder.add_labelled_edge("gate", "pond", 120)?;
+
+            assert_eq!(graph_builder.build()?.edges, vec![Edge::new(1, 2, 120)]);
+            Ok(())
+        }
+
+        #[test]
+        fn self_loop_not_allowed() {
+            let mut graph_builder =
+                create_test_graph_builder().with_validation_policy(ValidationPolicy::new().with_self_loops(false));
+            let invalid_edge: Edge = "2 2 50".parse().unwrap();
+
+            let expected = BuildGraphError::from(AddingEdgeError::SelfLoop {
+                edge: invalid_edge.to_string(),
+            });
 
             let actual = graph_builder.add_edge(invalid_edge).unwrap_err();
             assert_eq!(actual.to_string(), expected.to_string());
         }
+
+        #[test_case(GraphKind::Undirected, "2 1 70" => true; "undirected reversed")]
+        #[test_case(GraphKind::Directed, "2 1 70" => false; "directed reversed")]
+        #[test_case(GraphKind::Directed, "1 2 70" => true; "directed same direction")]
+        fn parallel_edge_not_allowed(kind: GraphKind, second_edge: &str) -> bool {
+            let mut graph_builder: GraphBuilder = GraphBuilder::new(GraphParameters::new(2, 2).with_kind(kind))
+                .with_validation_policy(ValidationPolicy::new().with_parallel_edges(false));
+            graph_builder.add_edge("1 2 50".parse().unwrap()).unwrap();
+
+            let second_edge: Edge = second_edge.parse().unwrap();
+
+            match graph_builder.add_edge(second_edge) {
+                Err(actual) => {
+                    let expected = BuildGraphError::from(AddingEdgeError::ParallelEdge {
+                        edge: second_edge.to_string(),
+                    });
+                    assert_eq!(actual.to_string(), expected.to_string());
+                    true
+                }
+                Ok(()) => false,
+            }
+        }
+    }
+
+    // -----------------------------------------------------------------------------
+
+    mod add_labelled_edge {
+        use super::*;
+
+        fn create_labelled_graph_builder() -> GraphBuilder {
+            GraphBuilder::new(GraphParameters::new(3, 3).with_labels())
+        }
+
+        #[test]
+        fn ok() -> BuildGraphResult<()> {
+            let mut graph_builder = create_labelled_graph_builder();
+            graph_builder.add_labelled_edge("gate", "pond_north", 120)?;
+            graph_builder.add_labelled_edge("pond_north", "fountain", 80)?;
+            graph_builder.add_labelled_edge("fountain", "gate", 60)?;
+
+            let graph = graph_builder.build()?;
+            let labels = graph.labels().unwrap();
+
+            assert_eq!(graph.edges[2], Edge::new(3, 1, 60));
+            assert_eq!(labels.iter().collect::<Vec<_>>(), vec![
+                (1, "gate"),
+                (2, "pond_north"),
+                (3, "fountain")
+            ]);
+            Ok(())
+        }
+
+        #[test]
+        fn too_many_node_labels() -> BuildGraphResult<()> {
+            let mut graph_builder = create_labelled_graph_builder();
+            graph_builder.add_labelled_edge("gate", "pond_north", 120)?;
+            graph_builder.add_labelled_edge("pond_north", "fountain", 80)?;
+
+            let expected = BuildGraphError::from(AddingEdgeError::TooManyNodeLabels {
+                edge:        "fountain garage 60".to_owned(),
+                label:       "garage".to_owned(),
+                nodes_count: 3,
+            });
+
+            let actual = graph_builder.add_labelled_edge("fountain", "garage", 60).unwrap_err();
+            assert_eq!(actual.to_string(), expected.to_string());
+            Ok(())
+        }
+
+        #[test]
+        fn too_many_edges_uses_labels() -> BuildGraphResult<()> {
+            let mut graph_builder = GraphBuilder::new(GraphParameters::new(3, 1).with_labels());
+            graph_builder.add_labelled_edge("gate", "pond_north", 120)?;
+
+            let expected = BuildGraphError::from(AddingEdgeError::TooManyEdges {
+                edges_count: 1,
+                edge:        "pond_north gate 50".to_owned(),
+            });
+
+            let actual = graph_builder.add_labelled_edge("pond_north", "gate", 50).unwrap_err();
+            assert_eq!(actual.to_string(), expected.to_string());
+            Ok(())
+        }
+
+        #[test]
+        fn node_labels_not_declared() {
+            let mut graph_builder = create_test_graph_builder();
+
+            let expected = BuildGraphError::from(AddingEdgeError::NodeLabelsNotDeclared {
+                edge: "gate pond_north 120".to_owned(),
+            });
+
+            let actual = graph_builder.add_labelled_edge("gate", "pond_north", 120).unwrap_err();
+            assert_eq!(actual.to_string(), expected.to_string());
+        }
     }
 
     // -----------------------------------------------------------------------------
@@ -466,20 +1498,45 @@ mod tests {
         #[test]
         fn ok() -> BuildGraphResult<()> {
             let mut graph_builder = create_test_graph_builder();
-            let first_edge = "1 3 100".parse()?;
+            let first_edge: Edge = "1 3 100".parse()?;
             let second_edge = "2 3 130".parse()?;
 
             graph_builder.add_edge("1 3 100".parse()?)?;
             graph_builder.add_edge("2 3 130".parse()?)?;
-            let expected = Graph {
-                nodes_count: 3,
-                edges:       vec![first_edge, second_edge],
-            };
+            let expected = Graph::new(3, vec![first_edge, second_edge], GraphKind::Undirected);
             let actual = graph_builder.build()?;
             assert_eq!(format!("{:?}", actual), format!("{:?}", expected));
             Ok(())
         }
 
+        #[test]
+        fn ok_directed() -> BuildGraphResult<()> {
+            let mut graph_builder: GraphBuilder =
+                GraphBuilder::new(GraphParameters::new(3, 3).with_kind(GraphKind::Directed));
+
+            graph_builder.add_edge("1 2 100".parse()?)?;
+            graph_builder.add_edge("2 3 100".parse()?)?;
+            graph_builder.add_edge("3 1 100".parse()?)?;
+
+            let actual = graph_builder.build()?;
+            assert_eq!(actual.kind, GraphKind::Directed);
+            Ok(())
+        }
+
+        #[test]
+        fn error_directed_graph_not_connected() -> BuildGraphResult<()> {
+            // the same edges make connected undirected graph
+            let mut graph_builder: GraphBuilder =
+                GraphBuilder::new(GraphParameters::new(3, 2).with_kind(GraphKind::Directed));
+
+            graph_builder.add_edge("1 2 100".parse()?)?;
+            graph_builder.add_edge("2 3 100".parse()?)?;
+
+            let actual = graph_builder.build().unwrap_err();
+            assert_eq!(actual.to_string(), BuildGraphError::GraphNotConnected.to_string());
+            Ok(())
+        }
+
         #[test]
         fn error_too_few_edges() {
             let mut graph_builder = create_test_graph_builder();
@@ -492,5 +1549,57 @@ mod tests {
             let actual = graph_builder.build().unwrap_err();
             assert_eq!(actual.to_string(), expected.to_string());
         }
+
+        #[test]
+        fn permissive_policy() -> BuildGraphResult<()> {
+            let mut graph_builder: GraphBuilder =
+                GraphBuilder::new(GraphParameters::new(4, 3)).with_validation_policy(ValidationPolicy::permissive());
+
+            // node 4 isn't connected and there are fewer edges than declared
+            graph_builder.add_edge("1 2 100".parse()?)?;
+            graph_builder.add_edge("3 3 100".parse()?)?;
+
+            let actual = graph_builder.build()?;
+            assert_eq!(actual.edges.len(), 2);
+            Ok(())
+        }
+    }
+
+    // -----------------------------------------------------------------------------
+
+    mod graph_parameters {
+        use super::*;
+        use test_case::test_case;
+
+        #[test_case("4 3", GraphKind::Undirected; "default kind")]
+        #[test_case("4 3 undirected", GraphKind::Undirected; "undirected")]
+        #[test_case("4 3 directed", GraphKind::Directed; "directed")]
+        fn ok(line: &str, expected_kind: GraphKind) -> BuildGraphResult<()> {
+            let actual = GraphParameters::try_from(line)?;
+
+            assert_eq!(actual.nodes_count, 4);
+            assert_eq!(actual.edges_count, 3);
+            assert_eq!(actual.kind, expected_kind);
+            Ok(())
+        }
+
+        #[test_case("4 3 labels", GraphKind::Undirected; "labels")]
+        #[test_case("4 3 directed labels", GraphKind::Directed; "directed with labels")]
+        #[test_case("4 3 labels directed", GraphKind::Directed; "labels before kind")]
+        fn ok_labelled(line: &str, expected_kind: GraphKind) -> BuildGraphResult<()> {
+            let actual = GraphParameters::try_from(line)?;
+
+            assert!(actual.labelled);
+            assert_eq!(actual.kind, expected_kind);
+            Ok(())
+        }
+
+        #[test]
+        fn fails_because_of_unknown_graph_option() {
+            let expected = BuildGraphError::from(GraphParametersParsingError::UnknownGraphOption("both".to_owned()));
+
+            let actual = GraphParameters::try_from("4 3 both").unwrap_err();
+            assert_eq!(actual.to_string(), expected.to_string());
+        }
     }
 }
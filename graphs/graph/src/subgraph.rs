@@ -0,0 +1,249 @@
+use crate::traversal::reachable;
+use crate::{Edge, Graph, Weight};
+
+/// Part of the graph with its nodes numbered from 1, returned by [`Graph::induced_subgraph`],
+/// [`Graph::filter_edges`] and [`Graph::reachable_subgraph`]
+///
+/// Nodes keep their order, so node with smaller index in the original graph has smaller index
+/// in the subgraph. Labels of the kept nodes are kept as well.
+///
+/// # Example
+/// ```
+/// use graph::Graph;
+///
+/// let graph: Graph = "4 3
+///     1 2 100
+///     2 3 400
+///     3 4 250"
+///     .parse()
+///     .unwrap();
+///
+/// let subgraph = graph.induced_subgraph([2, 3, 4]);
+///
+/// assert_eq!(subgraph.graph.nodes_count(), 3);
+/// assert_eq!(subgraph.graph.edges(), vec!["1 2 400".parse().unwrap(), "2 3 250".parse().unwrap()]);
+/// assert_eq!(subgraph.original_index(1), Some(2));
+/// assert_eq!(subgraph.index(4), Some(3));
+/// assert_eq!(subgraph.index(1), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Subgraph<W = i32> {
+    /// Graph containing only the kept nodes and edges
+    pub graph: Graph<W>,
+
+    /// Index of the node in the original graph, for node with index `i` stored at position `i - 1`
+    original_indices: Vec<u32>,
+
+    /// Index of the node in the subgraph for every node of the original graph (0 if node isn't kept)
+    indices: Vec<u32>,
+}
+
+impl<W: Weight> Subgraph<W> {
+    /// Returns index of the node in the original graph (or None if there is no such node in the subgraph)
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - index of the node in the subgraph
+    pub fn original_index(&self, index: u32) -> Option<u32> {
+        index
+            .checked_sub(1)
+            .and_then(|position| self.original_indices.get(position as usize))
+            .copied()
+    }
+
+    /// Returns index of the node in the subgraph (or None if the node isn't kept)
+    ///
+    /// # Arguments
+    ///
+    /// * `original_index` - index of the node in the original graph
+    pub fn index(&self, original_index: u32) -> Option<u32> {
+        self.indices
+            .get(original_index as usize)
+            .copied()
+            .filter(|index| *index != 0)
+    }
+
+    /// Returns indices of the nodes in the original graph, ordered by their indices in the subgraph
+    pub fn original_indices(&self) -> &[u32] {
+        &self.original_indices
+    }
+
+    // `kept_nodes` tells for every node of the graph (starting from index 0, which is never kept) if it's kept,
+    // only edges connecting kept nodes and accepted by `keep_edge` are kept
+    fn new<F>(graph: &Graph<W>, kept_nodes: &[bool], keep_edge: F) -> Subgraph<W>
+    where
+        F: Fn(&Edge<W>) -> bool,
+    {
+        let mut indices = vec![0; graph.nodes_count as usize + 1];
+        let mut original_indices = vec![];
+        for original_index in 1..=graph.nodes_count {
+            if kept_nodes[original_index as usize] {
+                original_indices.push(original_index);
+                indices[original_index as usize] = original_indices.len() as u32;
+            }
+        }
+
+        let edges = graph
+            .edges
+            .iter()
+            .filter(|edge| kept_nodes[edge.from_index as usize] && kept_nodes[edge.to_index as usize])
+            .filter(|edge| keep_edge(edge))
+            .map(|edge| {
+                Edge::new(
+                    indices[edge.from_index as usize],
+                    indices[edge.to_index as usize],
+                    edge.weight,
+                )
+            })
+            .collect();
+
+        let mut subgraph = Graph::new(original_indices.len() as u32, edges, graph.kind);
+
+        if let Some(labels) = graph.labels() {
+            subgraph = subgraph.with_labels(labels.restricted_to(&original_indices));
+        }
+
+        Subgraph {
+            graph: subgraph,
+            original_indices,
+            indices,
+        }
+    }
+}
+
+impl<W: Weight> Graph<W> {
+    /// Returns subgraph containing given nodes and all edges connecting them
+    ///
+    /// Indices of nodes that don't belong to the graph are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `nodes` - indices of the kept nodes (in any order)
+    pub fn induced_subgraph<I>(&self, nodes: I) -> Subgraph<W>
+    where
+        I: IntoIterator<Item = u32>,
+    {
+        let mut kept_nodes = vec![false; self.nodes_count as usize + 1];
+        for index in nodes {
+            if let Some(kept) = kept_nodes.get_mut(index as usize).filter(|_| index != 0) {
+                *kept = true;
+            }
+        }
+
+        Subgraph::new(self, &kept_nodes, |_| true)
+    }
+
+    /// Returns subgraph containing all nodes of the graph and edges accepted by `predicate`
+    ///
+    /// Nodes aren't removed, so indices in the subgraph are the same as in the original graph.
+    ///
+    /// # Example
+    /// ```
+    /// use graph::Graph;
+    ///
+    /// let graph: Graph = "3 3
+    ///     1 2 100
+    ///     2 3 400
+    ///     3 1 250"
+    ///     .parse()
+    ///     .unwrap();
+    ///
+    /// let short_cables = graph.filter_edges(|edge| edge.weight < 300);
+    ///
+    /// assert_eq!(short_cables.graph.nodes_count(), 3);
+    /// assert_eq!(short_cables.graph.edges(), vec!["1 2 100".parse().unwrap(), "3 1 250".parse().unwrap()]);
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - function telling if edge is kept
+    pub fn filter_edges<F>(&self, predicate: F) -> Subgraph<W>
+    where
+        F: Fn(&Edge<W>) -> bool,
+    {
+        let mut kept_nodes = vec![true; self.nodes_count as usize + 1];
+        kept_nodes[0] = false;
+
+        Subgraph::new(self, &kept_nodes, predicate)
+    }
+
+    /// Returns subgraph induced by nodes reachable from the given node (including the node itself)
+    ///
+    /// In directed graph edges are followed only in their direction. Subgraph is empty if there is no such node.
+    ///
+    /// # Example
+    /// ```
+    /// use graph::{build_graph_with_options, Graph, ReadOptions, ValidationPolicy};
+    ///
+    /// let graph: Graph = build_graph_with_options("4 2 directed
+    ///     1 2 100
+    ///     3 1 250", ReadOptions::new().with_policy(ValidationPolicy::permissive()))
+    ///     .unwrap();
+    ///
+    /// let reachable = graph.reachable_subgraph(1);
+    ///
+    /// assert_eq!(reachable.original_indices(), &[1, 2]);
+    /// assert_eq!(reachable.graph.edges(), vec!["1 2 100".parse().unwrap()]);
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `start_index` - index of the node the other nodes are reachable from
+    pub fn reachable_subgraph(&self, start_index: u32) -> Subgraph<W> {
+        if start_index == 0 || start_index > self.nodes_count {
+            return self.induced_subgraph(None);
+        }
+
+        Subgraph::new(self, &reachable(start_index, self.adjacency_list()), |_| true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph() -> Graph {
+        "5 5 labels
+            gate pond 100
+            pond fountain 400
+            fountain gate 250
+            garage shed 50
+            shed gate 700"
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn induced_subgraph_keeps_labels() {
+        let subgraph = graph().induced_subgraph([5, 1, 3, 1, 9, 0]);
+
+        assert_eq!(subgraph.original_indices(), &[1, 3, 5]);
+        assert_eq!(subgraph.graph.edges, vec![Edge::new(2, 1, 250), Edge::new(3, 1, 700)]);
+
+        let labels = subgraph.graph.labels().unwrap();
+        assert_eq!(labels.iter().collect::<Vec<_>>(), vec![
+            (1, "gate"),
+            (2, "fountain"),
+            (3, "shed")
+        ]);
+    }
+
+    #[test]
+    fn reachable_subgraph_of_undirected_graph() {
+        let graph = graph().filter_edges(|edge| edge.weight < 500).graph;
+
+        let subgraph = graph.reachable_subgraph(4);
+
+        assert_eq!(subgraph.original_indices(), &[4, 5]);
+        assert_eq!(subgraph.index(5), Some(2));
+        assert_eq!(subgraph.original_index(3), None);
+    }
+
+    #[test]
+    fn reachable_subgraph_of_unknown_node_is_empty() {
+        let subgraph = graph().reachable_subgraph(6);
+
+        assert_eq!(subgraph.graph.nodes_count, 0);
+        assert!(subgraph.graph.edges.is_empty());
+    }
+}
@@ -0,0 +1,290 @@
+use crate::structures::nodes_pair;
+use crate::{BuildGraphError, BuildGraphResult, Edge, Graph, GraphBuilder, GraphKind, GraphParameters, NodeLabels};
+use crate::{ValidationPolicy, Weight};
+use std::collections::HashSet;
+
+/// Structural transformations returning a new graph
+///
+/// Every transformed graph is built with [`GraphBuilder`] using [`ValidationPolicy::permissive`],
+/// so its edges are checked, but it doesn't have to be connected.
+impl<W: Weight> Graph<W> {
+    /// Returns graph with every edge going in the opposite direction
+    ///
+    /// Edges of undirected graph are only written the other way round.
+    ///
+    /// # Example
+    /// ```
+    /// use graph::Graph;
+    ///
+    /// let graph: Graph = "3 3 directed
+    ///     1 2 100
+    ///     2 3 80
+    ///     3 1 40"
+    ///     .parse()
+    ///     .unwrap();
+    ///
+    /// let reversed = graph.reversed().unwrap();
+    ///
+    /// assert_eq!(reversed.edges()[0], "2 1 100".parse().unwrap());
+    /// assert_eq!(reversed.neighbours(1).collect::<Vec<_>>(), vec![(3, 40)]);
+    /// ```
+    pub fn reversed(&self) -> BuildGraphResult<Graph<W>> {
+        let edges = self
+            .edges
+            .iter()
+            .map(|edge| Edge::new(edge.to_index, edge.from_index, edge.weight));
+
+        self.transformed(self.nodes_count, self.kind, edges, self.labels.clone())
+    }
+
+    /// Returns undirected graph with the same edges
+    ///
+    /// Edges connecting the same nodes in opposite directions become parallel edges.
+    pub fn to_undirected(&self) -> BuildGraphResult<Graph<W>> {
+        self.transformed(
+            self.nodes_count,
+            GraphKind::Undirected,
+            self.edges.iter().copied(),
+            self.labels.clone(),
+        )
+    }
+
+    /// Returns graph connecting exactly the nodes, which aren't connected in this graph
+    ///
+    /// Graph must be simple (without self-loops and parallel edges), otherwise
+    /// [`BuildGraphError::GraphNotSimple`] is returned. Complement doesn't contain self-loops either.
+    ///
+    /// # Example
+    /// ```
+    /// use graph::Graph;
+    ///
+    /// let graph: Graph = "3 2
+    ///     1 2 100
+    ///     2 3 80"
+    ///     .parse()
+    ///     .unwrap();
+    ///
+    /// let complement = graph.complement(50).unwrap();
+    ///
+    /// assert_eq!(complement.edges(), vec!["1 3 50".parse().unwrap()]);
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `weight` - weight of every edge of the complement
+    pub fn complement(&self, weight: W) -> BuildGraphResult<Graph<W>> {
+        let mut connected = HashSet::new();
+        for edge in &self.edges {
+            if edge.from_index == edge.to_index || !connected.insert(nodes_pair(edge, self.kind)) {
+                return Err(BuildGraphError::GraphNotSimple);
+            }
+        }
+
+        let kind = self.kind;
+        let nodes = 1..=self.nodes_count;
+        let edges = nodes
+            .clone()
+            .flat_map(|from_index| nodes.clone().map(move |to_index| (from_index, to_index)))
+            .filter(|(from_index, to_index)| match kind {
+                GraphKind::Directed => from_index != to_index,
+                GraphKind::Undirected => from_index < to_index,
+            })
+            .filter(|pair| !connected.contains(pair))
+            .map(|(from_index, to_index)| Edge::new(from_index, to_index, weight));
+
+        self.transformed(self.nodes_count, self.kind, edges, self.labels.clone())
+    }
+
+    /// Returns graph with given nodes replaced by one node
+    ///
+    /// Contracted node gets the smallest index of the given nodes and its label (if graph is labelled).
+    /// Other nodes keep their order, so indices of nodes greater than a removed one are decreased.
+    /// Edges connecting contracted nodes are removed, edges connecting them with other nodes lead
+    /// to the contracted node (so they might become parallel edges). Indices of nodes that don't belong
+    /// to the graph are skipped.
+    ///
+    /// # Example
+    /// ```
+    /// use graph::Graph;
+    ///
+    /// let graph: Graph = "4 4
+    ///     1 2 100
+    ///     2 3 80
+    ///     3 4 40
+    ///     4 2 60"
+    ///     .parse()
+    ///     .unwrap();
+    ///
+    /// let contracted = graph.contract_nodes([2, 4]).unwrap();
+    ///
+    /// assert_eq!(contracted.nodes_count(), 3);
+    /// assert_eq!(contracted.edges(), vec![
+    ///     "1 2 100".parse().unwrap(),
+    ///     "2 3 80".parse().unwrap(),
+    ///     "3 2 40".parse().unwrap(),
+    /// ]);
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `nodes` - indices of the contracted nodes (in any order)
+    pub fn contract_nodes<I>(&self, nodes: I) -> BuildGraphResult<Graph<W>>
+    where
+        I: IntoIterator<Item = u32>,
+    {
+        let mut contracted = vec![false; self.nodes_count as usize + 1];
+        for index in nodes {
+            if let Some(node) = contracted.get_mut(index as usize).filter(|_| index != 0) {
+                *node = true;
+            }
+        }
+
+        // new index of every node, contracted nodes get index of the first of them,
+        // `kept_nodes` contains old index of every new node
+        let mut indices = vec![0; self.nodes_count as usize + 1];
+        let mut contracted_index = None;
+        let mut kept_nodes = vec![];
+        for index in 1..=self.nodes_count {
+            indices[index as usize] = match contracted_index {
+                Some(contracted_index) if contracted[index as usize] => contracted_index,
+                _ => {
+                    kept_nodes.push(index);
+                    let new_index = kept_nodes.len() as u32;
+                    if contracted[index as usize] {
+                        contracted_index = Some(new_index);
+                    }
+                    new_index
+                }
+            };
+        }
+
+        let edges = self
+            .edges
+            .iter()
+            .filter(|edge| !(contracted[edge.from_index as usize] && contracted[edge.to_index as usize]))
+            .map(|edge| {
+                Edge::new(
+                    indices[edge.from_index as usize],
+                    indices[edge.to_index as usize],
+                    edge.weight,
+                )
+            });
+
+        let labels = self.labels().map(|labels| labels.restricted_to(&kept_nodes));
+
+        self.transformed(kept_nodes.len() as u32, self.kind, edges, labels)
+    }
+
+    /// Returns graph with nodes connected by the edge replaced by one node (see [`Graph::contract_nodes`])
+    ///
+    /// # Arguments
+    ///
+    /// * `edge` - edge connecting the contracted nodes
+    pub fn contract_edge(&self, edge: &Edge<W>) -> BuildGraphResult<Graph<W>> {
+        self.contract_nodes([edge.from_index, edge.to_index])
+    }
+
+    // builds the transformed graph, so its edges are checked
+    fn transformed<I>(
+        &self,
+        nodes_count: u32,
+        kind: GraphKind,
+        edges: I,
+        labels: Option<NodeLabels>,
+    ) -> BuildGraphResult<Graph<W>>
+    where
+        I: Iterator<Item = Edge<W>>,
+    {
+        let edges = edges.collect::<Vec<_>>();
+
+        let mut graph_builder = GraphBuilder::new(GraphParameters::new(nodes_count, edges.len()).with_kind(kind))
+            .with_validation_policy(ValidationPolicy::permissive());
+        for edge in edges {
+            graph_builder.add_edge(edge)?;
+        }
+
+        let graph = graph_builder.build()?;
+        Ok(match labels {
+            Some(labels) => graph.with_labels(labels),
+            None => graph,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{build_graph_with_options, ReadOptions};
+
+    fn graph() -> Graph {
+        let input = "4 4 directed labels
+            gate pond 100
+            pond fountain 80
+            fountain gate 40
+            fountain shed 60";
+
+        build_graph_with_options(input, ReadOptions::new().with_policy(ValidationPolicy::permissive())).unwrap()
+    }
+
+    #[test]
+    fn reversed_keeps_labels() -> BuildGraphResult<()> {
+        let reversed = graph().reversed()?;
+
+        assert_eq!(reversed.kind, GraphKind::Directed);
+        assert_eq!(reversed.edges[3], Edge::new(4, 3, 60));
+        assert_eq!(reversed.labels(), graph().labels());
+        Ok(())
+    }
+
+    #[test]
+    fn to_undirected() -> BuildGraphResult<()> {
+        let undirected = graph().to_undirected()?;
+
+        assert_eq!(undirected.kind, GraphKind::Undirected);
+        assert_eq!(undirected.neighbours(4).collect::<Vec<_>>(), vec![(3, 60)]);
+        Ok(())
+    }
+
+    #[test]
+    fn complement_of_directed_graph() -> BuildGraphResult<()> {
+        let complement = graph().complement(1)?;
+
+        assert_eq!(complement.edges.len(), 4 * 3 - 4);
+        assert!(!complement.edges.contains(&Edge::new(1, 2, 1)));
+        assert!(complement.edges.contains(&Edge::new(2, 1, 1)));
+        Ok(())
+    }
+
+    #[test]
+    fn complement_of_not_simple_graph() {
+        let mut graph = graph();
+        graph
+            .add_edge(graph.node_id(1).unwrap(), graph.node_id(2).unwrap(), 30)
+            .unwrap();
+
+        let expected = BuildGraphError::GraphNotSimple;
+        let actual = graph.complement(1).unwrap_err();
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn contract_edge() -> BuildGraphResult<()> {
+        let graph = graph();
+        let contracted = graph.contract_edge(&graph.edges[0])?;
+
+        assert_eq!(contracted.nodes_count, 3);
+        assert_eq!(contracted.edges, vec![
+            Edge::new(1, 2, 80),
+            Edge::new(2, 1, 40),
+            Edge::new(2, 3, 60)
+        ]);
+
+        let labels = contracted.labels().unwrap();
+        assert_eq!(labels.iter().collect::<Vec<_>>(), vec![
+            (1, "gate"),
+            (2, "fountain"),
+            (3, "shed")
+        ]);
+        Ok(())
+    }
+}
@@ -0,0 +1,577 @@
+use crate::{AdjacencyList, Edge, Graph, GraphKind, Neighbours, Weight};
+use std::collections::VecDeque;
+use std::ops::RangeInclusive;
+
+/// Event of Depth First Search, yielded by [`DfsEvents`] and passed to [`DfsVisitor`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DfsEvent<W = i32> {
+    /// Node was reached for the first time
+    Discover(u32),
+
+    /// Edge leads to a node reached for the first time (it's followed by [`DfsEvent::Discover`] of this node)
+    TreeEdge(Edge<W>),
+
+    /// Edge leads to a node, which search hasn't finished yet (so the edge closes a cycle)
+    BackEdge(Edge<W>),
+
+    /// Edge of directed graph leads to an already finished node
+    ForwardOrCrossEdge(Edge<W>),
+
+    /// All edges of the node were examined
+    Finish(u32),
+}
+
+/// Receiver of [`DfsEvent`]s, used by [`Graph::visit_depth_first`]
+///
+/// All methods do nothing by default, so only the interesting events have to be handled.
+///
+/// # Example
+/// ```
+/// use graph::{build_graph_with_options, DfsVisitor, Edge, Graph, ReadOptions, ValidationPolicy};
+///
+/// // nodes in reversed order of finishing are topologically sorted, if there are no back edges
+/// #[derive(Default)]
+/// struct TopologicalSort {
+///     order: Vec<u32>,
+///     has_cycle: bool,
+/// }
+///
+/// impl DfsVisitor for TopologicalSort {
+///     fn back_edge(&mut self, _edge: Edge) {
+///         self.has_cycle = true;
+///     }
+///
+///     fn finish(&mut self, index: u32) {
+///         self.order.insert(0, index);
+///     }
+/// }
+///
+/// let graph: Graph = build_graph_with_options("4 3 directed
+///     4 2 100
+///     2 1 80
+///     3 1 40", ReadOptions::new().with_policy(ValidationPolicy::permissive()))
+///     .unwrap();
+///
+/// let mut visitor = TopologicalSort::default();
+/// graph.visit_depth_first_all(&mut visitor);
+///
+/// assert!(!visitor.has_cycle);
+/// assert_eq!(visitor.order, vec![4, 3, 2, 1]);
+///
+/// let graph: Graph = "3 3 directed
+///     1 2 100
+///     2 3 80
+///     3 1 40"
+///     .parse()
+///     .unwrap();
+///
+/// let mut visitor = TopologicalSort::default();
+/// graph.visit_depth_first_all(&mut visitor);
+///
+/// assert!(visitor.has_cycle);
+/// ```
+pub trait DfsVisitor<W = i32> {
+    /// Node was reached for the first time
+    fn discover(&mut self, _index: u32) {}
+
+    /// Edge leads to a node reached for the first time
+    fn tree_edge(&mut self, _edge: Edge<W>) {}
+
+    /// Edge leads to a node, which search hasn't finished yet
+    ///
+    /// In undirected graph every edge which isn't a tree edge is reported once, as a back edge
+    /// from the descendant to the ancestor. The edge used to reach the node isn't reported again,
+    /// but the edges parallel to it are.
+    fn back_edge(&mut self, _edge: Edge<W>) {}
+
+    /// Edge of directed graph leads to an already finished node
+    fn forward_or_cross_edge(&mut self, _edge: Edge<W>) {}
+
+    /// All edges of the node were examined
+    fn finish(&mut self, _index: u32) {}
+}
+
+impl<W> DfsEvent<W> {
+    /// Passes the event to the matching method of `visitor`
+    ///
+    /// # Arguments
+    ///
+    /// * `visitor` - receiver of the event
+    pub fn visit<V: DfsVisitor<W> + ?Sized>(self, visitor: &mut V) {
+        match self {
+            DfsEvent::Discover(index) => visitor.discover(index),
+            DfsEvent::TreeEdge(edge) => visitor.tree_edge(edge),
+            DfsEvent::BackEdge(edge) => visitor.back_edge(edge),
+            DfsEvent::ForwardOrCrossEdge(edge) => visitor.forward_or_cross_edge(edge),
+            DfsEvent::Finish(index) => visitor.finish(index),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NodeState {
+    Undiscovered,
+    Active,
+    Finished,
+}
+
+// node on the search stack with its neighbours left to examine
+#[derive(Clone)]
+struct Frame<'a, W> {
+    index:          u32,
+    neighbours:     Neighbours<'a, W>,
+    // node the search came from - the first edge leading back to it is the tree edge seen from the other end
+    parent:         Option<u32>,
+    // self-loop of undirected graph is twice in neighbours of the node, only the first copy is reported
+    self_loop_copy: bool,
+}
+
+/// Lazy iterator over [`DfsEvent`]s of Depth First Search
+///
+/// Search is non-recursive, neighbours are examined in order of [`AdjacencyList::neighbours`].
+/// After finishing all nodes reachable from the start, search may continue from the next undiscovered nodes
+/// (see [`Graph::dfs_events_all`]).
+///
+/// # Example
+/// ```
+/// use graph::{DfsEvent, Graph};
+///
+/// let graph: Graph = "3 3 directed
+///     1 2 100
+///     2 3 80
+///     3 1 40"
+///     .parse()
+///     .unwrap();
+///
+/// let back_edges = graph
+///     .dfs_events(1)
+///     .filter(|event| matches!(event, DfsEvent::BackEdge(_)))
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(back_edges, vec![DfsEvent::BackEdge("3 1 40".parse().unwrap())]);
+/// ```
+#[derive(Clone)]
+pub struct DfsEvents<'a, W = i32> {
+    adjacency_list: &'a AdjacencyList<W>,
+    kind:           GraphKind,
+    roots:          RangeInclusive<u32>,
+    states:         Vec<NodeState>,
+    stack:          Vec<Frame<'a, W>>,
+    // discover event following the tree edge
+    pending:        Option<DfsEvent<W>>,
+}
+
+impl<'a, W: Weight> DfsEvents<'a, W> {
+    /// Creates search starting in the node with given index
+    ///
+    /// Search yields no events if there is no node with `start_index`.
+    ///
+    /// # Arguments
+    ///
+    /// * `adjacency_list` - [`AdjacencyList`] containing data about graph edges
+    /// * `kind` - [`GraphKind`] of the graph, which decides how the edges are reported
+    /// * `start_index` - index of the node the search starts from
+    pub fn new(adjacency_list: &'a AdjacencyList<W>, kind: GraphKind, start_index: u32) -> DfsEvents<'a, W> {
+        let mut roots = start_index..=start_index;
+        // there is nothing to search, if the start node doesn't exist
+        if !contains_node(adjacency_list, start_index) {
+            roots.next();
+        }
+
+        Self::with_roots(adjacency_list, kind, roots)
+    }
+
+    // search starts in every root, which isn't discovered yet
+    fn with_roots(adjacency_list: &'a AdjacencyList<W>, kind: GraphKind, roots: RangeInclusive<u32>) -> Self {
+        DfsEvents {
+            adjacency_list,
+            kind,
+            roots,
+            states: vec![NodeState::Undiscovered; adjacency_list.nodes_count() as usize + 1],
+            stack: vec![],
+            pending: None,
+        }
+    }
+
+    fn discover(&mut self, index: u32, parent: Option<u32>) -> DfsEvent<W> {
+        self.states[index as usize] = NodeState::Active;
+        self.stack.push(Frame {
+            index,
+            neighbours: self.adjacency_list.neighbours(index),
+            parent,
+            self_loop_copy: false,
+        });
+
+        DfsEvent::Discover(index)
+    }
+
+    // returns None if the edge isn't reported
+    fn classify(&mut self, edge: Edge<W>) -> Option<DfsEvent<W>> {
+        let undirected = self.kind == GraphKind::Undirected;
+
+        match self.states[edge.to_index as usize] {
+            NodeState::Undiscovered => {
+                self.pending = Some(self.discover(edge.to_index, Some(edge.from_index)));
+                Some(DfsEvent::TreeEdge(edge))
+            }
+            NodeState::Active if undirected => {
+                let frame = self.stack.last_mut()?;
+                if frame.parent == Some(edge.to_index) {
+                    frame.parent = None;
+                    return None;
+                }
+                if edge.from_index == edge.to_index {
+                    frame.self_loop_copy = !frame.self_loop_copy;
+                    if !frame.self_loop_copy {
+                        return None;
+                    }
+                }
+
+                Some(DfsEvent::BackEdge(edge))
+            }
+            NodeState::Active => Some(DfsEvent::BackEdge(edge)),
+            // in undirected graph the edge was already reported as a back edge of the finished node
+            NodeState::Finished if undirected => None,
+            NodeState::Finished => Some(DfsEvent::ForwardOrCrossEdge(edge)),
+        }
+    }
+}
+
+impl<'a, W: Weight> Iterator for DfsEvents<'a, W> {
+    type Item = DfsEvent<W>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(event) = self.pending.take() {
+            return Some(event);
+        }
+
+        loop {
+            let frame = match self.stack.last_mut() {
+                Some(frame) => frame,
+                None => {
+                    let states = &self.states;
+                    let root = self
+                        .roots
+                        .find(|index| states[*index as usize] == NodeState::Undiscovered)?;
+
+                    return Some(self.discover(root, None));
+                }
+            };
+
+            match frame.neighbours.next() {
+                Some((to_index, weight)) => {
+                    let edge = Edge::new(frame.index, to_index, weight);
+                    if let Some(event) = self.classify(edge) {
+                        return Some(event);
+                    }
+                }
+                None => {
+                    let index = frame.index;
+                    self.stack.pop();
+                    self.states[index as usize] = NodeState::Finished;
+
+                    return Some(DfsEvent::Finish(index));
+                }
+            }
+        }
+    }
+}
+
+/// Lazy iterator over indices of nodes in order of Depth First Search (preorder)
+///
+/// Yields only the nodes reachable from the start node.
+#[derive(Clone)]
+pub struct Dfs<'a, W = i32>(DfsEvents<'a, W>);
+
+impl<'a, W: Weight> Dfs<'a, W> {
+    /// Creates search starting in the node with given index
+    ///
+    /// Search yields no nodes if there is no node with `start_index`.
+    ///
+    /// # Arguments
+    ///
+    /// * `adjacency_list` - [`AdjacencyList`] containing data about graph edges
+    /// * `start_index` - index of the node the search starts from
+    pub fn new(adjacency_list: &'a AdjacencyList<W>, start_index: u32) -> Dfs<'a, W> {
+        // kind changes only the reported edges, not the order of nodes
+        Dfs(DfsEvents::new(adjacency_list, GraphKind::Directed, start_index))
+    }
+}
+
+impl<'a, W: Weight> Iterator for Dfs<'a, W> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.by_ref().find_map(|event| match event {
+            DfsEvent::Discover(index) => Some(index),
+            _ => None,
+        })
+    }
+}
+
+/// Lazy iterator over indices of nodes in order of Breadth First Search
+///
+/// Yields only the nodes reachable from the start node, every node after all nodes closer to the start.
+#[derive(Clone)]
+pub struct Bfs<'a, W = i32> {
+    adjacency_list: &'a AdjacencyList<W>,
+    discovered:     Vec<bool>,
+    queue:          VecDeque<u32>,
+}
+
+impl<'a, W: Weight> Bfs<'a, W> {
+    /// Creates search starting in the node with given index
+    ///
+    /// Search yields no nodes if there is no node with `start_index`.
+    ///
+    /// # Arguments
+    ///
+    /// * `adjacency_list` - [`AdjacencyList`] containing data about graph edges
+    /// * `start_index` - index of the node the search starts from
+    pub fn new(adjacency_list: &'a AdjacencyList<W>, start_index: u32) -> Bfs<'a, W> {
+        let mut discovered = vec![false; adjacency_list.nodes_count() as usize + 1];
+        let mut queue = VecDeque::new();
+        if contains_node(adjacency_list, start_index) {
+            discovered[start_index as usize] = true;
+            queue.push_back(start_index);
+        }
+
+        Bfs {
+            adjacency_list,
+            discovered,
+            queue,
+        }
+    }
+}
+
+impl<'a, W: Weight> Iterator for Bfs<'a, W> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.queue.pop_front()?;
+
+        for (neighbour, _) in self.adjacency_list.neighbours(index) {
+            if !self.discovered[neighbour as usize] {
+                self.discovered[neighbour as usize] = true;
+                self.queue.push_back(neighbour);
+            }
+        }
+
+        Some(index)
+    }
+}
+
+impl<W: Weight> Graph<W> {
+    /// Returns lazy iterator over nodes reachable from the start node, in order of Breadth First Search
+    ///
+    /// Edges of directed graph are followed only in their direction. Iterator is empty if there is no node
+    /// with `start_index`.
+    ///
+    /// # Example
+    /// ```
+    /// use graph::Graph;
+    ///
+    /// let graph: Graph = "5 4
+    ///     1 2 100
+    ///     2 3 80
+    ///     1 4 40
+    ///     4 5 70"
+    ///     .parse()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(graph.bfs(1).collect::<Vec<_>>(), vec![1, 2, 4, 3, 5]);
+    /// assert_eq!(graph.dfs(1).collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+    /// assert_eq!(graph.bfs(6).next(), None);
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `start_index` - index of the node the search starts from
+    pub fn bfs(&self, start_index: u32) -> Bfs<'_, W> {
+        Bfs::new(self.adjacency_list(), start_index)
+    }
+
+    /// Returns lazy iterator over nodes reachable from the start node, in order of Depth First Search
+    ///
+    /// Edges of directed graph are followed only in their direction. Iterator is empty if there is no node
+    /// with `start_index`. See [`Graph::bfs`] for example.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_index` - index of the node the search starts from
+    pub fn dfs(&self, start_index: u32) -> Dfs<'_, W> {
+        Dfs::new(self.adjacency_list(), start_index)
+    }
+
+    /// Returns lazy iterator over [`DfsEvent`]s of Depth First Search from the start node
+    ///
+    /// Iterator is empty if there is no node with `start_index`.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_index` - index of the node the search starts from
+    pub fn dfs_events(&self, start_index: u32) -> DfsEvents<'_, W> {
+        DfsEvents::new(self.adjacency_list(), self.kind, start_index)
+    }
+
+    /// Returns lazy iterator over [`DfsEvent`]s of Depth First Search covering all nodes
+    ///
+    /// Search starts from the first node and when it's finished, it continues from the undiscovered node
+    /// with the smallest index.
+    pub fn dfs_events_all(&self) -> DfsEvents<'_, W> {
+        DfsEvents::with_roots(self.adjacency_list(), self.kind, 1..=self.nodes_count)
+    }
+
+    /// Passes [`DfsEvent`]s of Depth First Search from the start node to `visitor`
+    ///
+    /// No events are passed if there is no node with `start_index`.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_index` - index of the node the search starts from
+    /// * `visitor` - receiver of the events
+    pub fn visit_depth_first<V: DfsVisitor<W> + ?Sized>(&self, start_index: u32, visitor: &mut V) {
+        self.dfs_events(start_index).for_each(|event| event.visit(visitor));
+    }
+
+    /// Passes [`DfsEvent`]s of Depth First Search covering all nodes to `visitor` (see [`Graph::dfs_events_all`])
+    ///
+    /// # Arguments
+    ///
+    /// * `visitor` - receiver of the events
+    pub fn visit_depth_first_all<V: DfsVisitor<W> + ?Sized>(&self, visitor: &mut V) {
+        self.dfs_events_all().for_each(|event| event.visit(visitor));
+    }
+}
+
+fn contains_node<W: Weight>(adjacency_list: &AdjacencyList<W>, index: u32) -> bool {
+    (1..=adjacency_list.nodes_count()).contains(&index)
+}
+
+/// Marks nodes reachable from the start node, value at position `i` tells if node `i` is reachable
+///
+/// # Arguments
+///
+/// * `start_index` - index of the node the search starts from
+/// * `adjacency_list` - [`crate::AdjacencyList`] containing data about graph edges
+pub(crate) fn reachable<W: Weight>(start_index: u32, adjacency_list: &AdjacencyList<W>) -> Vec<bool> {
+    let mut visited = vec![false; adjacency_list.nodes_count() as usize + 1];
+
+    for index in Dfs::new(adjacency_list, start_index) {
+        visited[index as usize] = true;
+    }
+
+    visited
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{build_graph_with_options, ReadOptions, ValidationPolicy};
+    use std::collections::HashMap;
+    use test_case::test_case;
+
+    fn graph(input: &str) -> Graph {
+        build_graph_with_options(input, ReadOptions::new().with_policy(ValidationPolicy::permissive())).unwrap()
+    }
+
+    // bridges are tree edges, which descendants don't have back edges to the ancestors of the edge
+    #[derive(Default)]
+    struct Bridges {
+        discovery: HashMap<u32, usize>,
+        low:       HashMap<u32, usize>,
+        parents:   HashMap<u32, u32>,
+        bridges:   Vec<(u32, u32)>,
+    }
+
+    impl DfsVisitor for Bridges {
+        fn discover(&mut self, index: u32) {
+            let time = self.discovery.len();
+            self.discovery.insert(index, time);
+            self.low.insert(index, time);
+        }
+
+        fn tree_edge(&mut self, edge: Edge) {
+            self.parents.insert(edge.to_index, edge.from_index);
+        }
+
+        fn back_edge(&mut self, edge: Edge) {
+            let low = self.low[&edge.from_index].min(self.discovery[&edge.to_index]);
+            self.low.insert(edge.from_index, low);
+        }
+
+        fn finish(&mut self, index: u32) {
+            if let Some(parent) = self.parents.get(&index).copied() {
+                let low = self.low[&parent].min(self.low[&index]);
+                self.low.insert(parent, low);
+
+                if self.low[&index] > self.discovery[&parent] {
+                    self.bridges.push((parent, index));
+                }
+            }
+        }
+    }
+
+    #[test_case("4 4\n1 2 10\n2 3 10\n3 1 10\n3 4 10" => vec![(3, 4)]; "cycle with tail")]
+    #[test_case("3 3\n1 2 10\n2 3 10\n3 2 10" => vec![(1, 2)]; "parallel edges are not bridges")]
+    #[test_case("3 3\n1 2 10\n2 2 10\n2 3 10" => vec![(2, 3), (1, 2)]; "self-loop")]
+    #[test_case("5 3\n1 2 10\n3 4 10\n4 5 10" => vec![(1, 2), (4, 5), (3, 4)]; "disconnected graph")]
+    fn bridges(input: &str) -> Vec<(u32, u32)> {
+        let mut visitor = Bridges::default();
+        graph(input).visit_depth_first_all(&mut visitor);
+        visitor.bridges
+    }
+
+    #[test]
+    fn undirected_events() {
+        let events = graph("3 4\n1 2 10\n2 3 20\n3 1 30\n3 3 40")
+            .dfs_events(1)
+            .collect::<Vec<_>>();
+
+        assert_eq!(events, vec![
+            DfsEvent::Discover(1),
+            DfsEvent::TreeEdge(Edge::new(1, 2, 10)),
+            DfsEvent::Discover(2),
+            DfsEvent::TreeEdge(Edge::new(2, 3, 20)),
+            DfsEvent::Discover(3),
+            DfsEvent::BackEdge(Edge::new(3, 1, 30)),
+            DfsEvent::BackEdge(Edge::new(3, 3, 40)),
+            DfsEvent::Finish(3),
+            DfsEvent::Finish(2),
+            DfsEvent::Finish(1),
+        ]);
+    }
+
+    #[test]
+    fn directed_events() {
+        let events = graph("4 4 directed\n1 2 10\n1 3 20\n3 2 30\n2 1 40")
+            .dfs_events_all()
+            .filter(|event| !matches!(event, DfsEvent::Discover(_) | DfsEvent::Finish(_)))
+            .collect::<Vec<_>>();
+
+        assert_eq!(events, vec![
+            DfsEvent::TreeEdge(Edge::new(1, 2, 10)),
+            DfsEvent::BackEdge(Edge::new(2, 1, 40)),
+            DfsEvent::TreeEdge(Edge::new(1, 3, 20)),
+            DfsEvent::ForwardOrCrossEdge(Edge::new(3, 2, 30)),
+        ]);
+    }
+
+    #[test]
+    fn directed_traversal_follows_edges_direction() {
+        let graph = graph("4 3 directed\n2 1 10\n2 3 10\n3 4 10");
+
+        assert_eq!(graph.bfs(2).collect::<Vec<_>>(), vec![2, 1, 3, 4]);
+        assert_eq!(graph.dfs(3).collect::<Vec<_>>(), vec![3, 4]);
+    }
+
+    #[test_case(0; "zero index")]
+    #[test_case(5; "index greater than nodes count")]
+    fn unknown_start_node(start_index: u32) {
+        let graph = graph("4 3\n1 2 10\n2 3 10\n3 4 10");
+
+        assert_eq!(graph.bfs(start_index).count(), 0);
+        assert_eq!(graph.dfs(start_index).count(), 0);
+        assert_eq!(graph.dfs_events(start_index).count(), 0);
+    }
+}
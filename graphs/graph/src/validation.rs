@@ -0,0 +1,108 @@
+/// Tells how the number of edges added to [`crate::GraphBuilder`] is compared with the declared number of edges
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum EdgesCountPolicy {
+    /// Graph must have exactly the declared number of edges
+    #[default]
+    Exact,
+
+    /// Graph may have fewer edges than declared, but not more
+    AtMost,
+}
+
+/// Set of checks done by [`crate::GraphBuilder`] while adding edges and building the graph
+///
+/// Default policy requires connected graph with exactly the declared number of edges,
+/// self-loops and parallel edges are allowed. Algorithms check their own preconditions,
+/// so graphs for algorithms that don't need connectivity can be built with [`ValidationPolicy::permissive`].
+///
+/// # Example
+/// ```
+/// use graph::{build_graph_with_options, EdgesCountPolicy, Graph, ReadOptions, ValidationPolicy};
+///
+/// let policy = ValidationPolicy::permissive().with_parallel_edges(false);
+///
+/// let graph: Graph = build_graph_with_options("4 3
+///     1 2 100
+///     3 4 80", ReadOptions::new().with_policy(policy))
+///     .unwrap();
+///
+/// assert_eq!(graph.edges().len(), 2);
+///
+/// let result: graph::BuildGraphResult<Graph> = build_graph_with_options("2 2
+///     1 2 100
+///     2 1 80", ReadOptions::new().with_policy(policy));
+///
+/// assert!(result.is_err());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ValidationPolicy {
+    /// Tells if there must be a path from any node to any other node (following edge directions in directed graph)
+    pub require_connected: bool,
+
+    /// Tells how the number of added edges is compared with the declared number
+    pub edges_count: EdgesCountPolicy,
+
+    /// Tells if edges connecting node with itself can be added
+    pub allow_self_loops: bool,
+
+    /// Tells if more than one edge connecting the same pair of nodes can be added
+    /// (in the same direction, if graph is directed)
+    pub allow_parallel_edges: bool,
+}
+
+impl Default for ValidationPolicy {
+    fn default() -> Self {
+        ValidationPolicy {
+            require_connected:    true,
+            edges_count:          EdgesCountPolicy::Exact,
+            allow_self_loops:     true,
+            allow_parallel_edges: true,
+        }
+    }
+}
+
+impl ValidationPolicy {
+    /// Creates default policy
+    pub fn new() -> ValidationPolicy {
+        ValidationPolicy::default()
+    }
+
+    /// Creates policy accepting any graph, which has at most the declared number of edges with valid indices
+    pub fn permissive() -> ValidationPolicy {
+        ValidationPolicy {
+            require_connected:    false,
+            edges_count:          EdgesCountPolicy::AtMost,
+            allow_self_loops:     true,
+            allow_parallel_edges: true,
+        }
+    }
+
+    /// Sets if graph must be connected
+    pub fn with_connectivity(self, require_connected: bool) -> ValidationPolicy {
+        ValidationPolicy {
+            require_connected,
+            ..self
+        }
+    }
+
+    /// Sets how the number of added edges is compared with the declared number
+    pub fn with_edges_count(self, edges_count: EdgesCountPolicy) -> ValidationPolicy {
+        ValidationPolicy { edges_count, ..self }
+    }
+
+    /// Sets if self-loops can be added
+    pub fn with_self_loops(self, allow_self_loops: bool) -> ValidationPolicy {
+        ValidationPolicy {
+            allow_self_loops,
+            ..self
+        }
+    }
+
+    /// Sets if parallel edges can be added
+    pub fn with_parallel_edges(self, allow_parallel_edges: bool) -> ValidationPolicy {
+        ValidationPolicy {
+            allow_parallel_edges,
+            ..self
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use std::cmp::Ordering;
+use std::convert::TryInto;
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::num::ParseFloatError;
+use std::ops::Add;
+use std::str::FromStr;
+
+/// Type of the edge weight
+///
+/// Weights must be totally ordered (so they can be sorted and compared by the algorithms)
+/// and must be parsable from graph description file.
+///
+/// Implemented for primitive integers and [`OrderedFloat`].
+///
+/// # Example
+/// ```
+/// use graph::{Graph, OrderedFloat};
+///
+/// let graph: Graph<u64> = "2 1
+///     1 2 5000000000"
+///     .parse()
+///     .unwrap();
+///
+/// assert_eq!(graph.edges()[0].weight, 5_000_000_000);
+///
+/// let graph: Graph<OrderedFloat> = "2 1
+///     1 2 12.75"
+///     .parse()
+///     .unwrap();
+///
+/// assert_eq!(graph.edges()[0].weight, OrderedFloat(12.75));
+/// ```
+pub trait Weight: Copy + Debug + Display + FromStr + Ord + Add<Output = Self> + Send + Sync + 'static {
+    /// Weight of an empty path (additive identity)
+    fn zero() -> Self;
+
+    /// Checks if weight is less than [`Weight::zero`]
+    fn is_negative(&self) -> bool {
+        *self < Self::zero()
+    }
+
+    /// Adds weights, returns None if the sum overflows
+    ///
+    /// Default implementation never fails, so it should be overridden for types which can overflow.
+    fn checked_add(self, other: Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    /// Identifies weight type in binary graph format (see [`crate::write_binary`])
+    ///
+    /// Value `0` means that weight is written as text, which works for any weight type.
+    const BINARY_TAG: u8 = 0;
+
+    /// Appends weight to the buffer in binary graph format
+    fn write_binary(&self, buffer: &mut Vec<u8>) {
+        let text = self.to_string();
+        buffer.extend_from_slice(&(text.len() as u32).to_le_bytes());
+        buffer.extend_from_slice(text.as_bytes());
+    }
+
+    /// Reads weight written by [`Weight::write_binary`] from the beginning of `bytes`
+    ///
+    /// Returns the weight and number of read bytes or None if bytes don't contain valid weight
+    fn read_binary(bytes: &[u8]) -> Option<(Self, usize)> {
+        let length = u32::from_le_bytes(bytes.get(..4)?.try_into().ok()?) as usize;
+        let text = std::str::from_utf8(bytes.get(4..4 + length)?).ok()?;
+
+        Some((text.parse().ok()?, 4 + length))
+    }
+}
+
+macro_rules! impl_weight_for_integers {
+    ($($integer:ty => $tag:expr),*) => {
+        $(
+            impl Weight for $integer {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$integer>::checked_add(self, other)
+                }
+
+                const BINARY_TAG: u8 = $tag;
+
+                fn write_binary(&self, buffer: &mut Vec<u8>) {
+                    buffer.extend_from_slice(&self.to_le_bytes());
+                }
+
+                fn read_binary(bytes: &[u8]) -> Option<(Self, usize)> {
+                    const SIZE: usize = std::mem::size_of::<$integer>();
+                    let bytes = bytes.get(..SIZE)?.try_into().ok()?;
+
+                    Some((<$integer>::from_le_bytes(bytes), SIZE))
+                }
+            }
+        )*
+    };
+}
+
+impl_weight_for_integers!(i32 => 1, i64 => 2, u32 => 3, u64 => 4);
+
+// size of usize depends on the platform, so it's written as u64
+impl Weight for usize {
+    const BINARY_TAG: u8 = 5;
+
+    fn zero() -> Self {
+        0
+    }
+
+    fn checked_add(self, other: Self) -> Option<Self> {
+        usize::checked_add(self, other)
+    }
+
+    fn write_binary(&self, buffer: &mut Vec<u8>) {
+        (*self as u64).write_binary(buffer)
+    }
+
+    fn read_binary(bytes: &[u8]) -> Option<(Self, usize)> {
+        let (value, size) = u64::read_binary(bytes)?;
+
+        Some((value.try_into().ok()?, size))
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// Floating-point number with total ordering, so it could be used as [`Weight`]
+///
+/// Values are compared using [`f64::total_cmp`]
+#[derive(Copy, Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+pub struct OrderedFloat(pub f64);
+
+impl OrderedFloat {
+    /// Returns wrapped value
+    pub fn value(&self) -> f64 {
+        self.0
+    }
+}
+
+impl PartialEq for OrderedFloat {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OrderedFloat {}
+
+impl PartialOrd for OrderedFloat {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrderedFloat {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl Add for OrderedFloat {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        OrderedFloat(self.0 + other.0)
+    }
+}
+
+impl Display for OrderedFloat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl FromStr for OrderedFloat {
+    type Err = ParseFloatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(OrderedFloat)
+    }
+}
+
+impl Weight for OrderedFloat {
+    const BINARY_TAG: u8 = 6;
+
+    fn zero() -> Self {
+        OrderedFloat(0.0)
+    }
+
+    fn write_binary(&self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(&self.0.to_le_bytes());
+    }
+
+    fn read_binary(bytes: &[u8]) -> Option<(Self, usize)> {
+        let bytes = bytes.get(..8)?.try_into().ok()?;
+
+        Some((OrderedFloat(f64::from_le_bytes(bytes)), 8))
+    }
+}
@@ -1,4 +1,12 @@
-use graph::{build_graph, AddingEdgeError, BuildGraphError, Edge, GraphParametersParsingError, ParsingEdgeError};
+use graph::{
+    build_graph,
+    AddingEdgeError,
+    BuildGraphError,
+    DimacsParsingError,
+    Edge,
+    GraphParametersParsingError,
+    ParsingEdgeError,
+};
 use std::path::PathBuf;
 use test_case::test_case;
 
@@ -15,7 +23,7 @@ fn build_path(dir: &str, graph_file: &str) -> PathBuf {
 
 fn validate_graph_file(dir_name: &str, graph_file: &str, expected_error: BuildGraphError) {
     let path = build_path(dir_name, graph_file);
-    let actual_error = build_graph(&path).unwrap_err();
+    let actual_error = build_graph::<i32, _>(&path).unwrap_err();
 
     assert_eq!(actual_error.to_string(), expected_error.to_string());
 }
@@ -57,6 +65,10 @@ macro_rules! function {
             GraphParametersParsingError::EdgesCountValueIsNotInteger("X".to_owned());
             "error_parsing_graph_parameters_edges_count"
 )]
+#[test_case("error_parsing_graph_parameters_unknown_graph_kind",
+            GraphParametersParsingError::UnknownGraphOption("bidirectional".to_owned());
+            "error_parsing_graph_parameters_unknown_graph_kind"
+)]
 fn parsing_graph_parameters_errors(graph_file: &str, expected_error: GraphParametersParsingError) {
     validate_graph_file(function!(), graph_file, BuildGraphError::from(expected_error));
 }
@@ -65,56 +77,116 @@ fn parsing_graph_parameters_errors(graph_file: &str, expected_error: GraphParame
 // When lines: 2..EOF in the graph description file are invalid
 // -----------------------------------------------------------------------------
 
-#[test_case("error_parsing_edge_non_integer_from_index", 2,
+#[test_case("error_parsing_edge_non_integer_from_index", 3,
             ParsingEdgeError::FromIndexValueMustBeInteger("xyz".to_owned());
             "error_parsing_edge_non_integer_from_index"
 )]
-#[test_case("error_parsing_edge_non_integer_to_index", 2,
+#[test_case("error_parsing_edge_non_integer_to_index", 3,
             ParsingEdgeError::ToIndexValueMustBeInteger("abc".to_owned());
             "error_parsing_edge_non_integer_to_index"
 )]
-#[test_case("error_parsing_edge_non_integer_weight", 2,
+#[test_case("error_parsing_edge_non_integer_weight", 3,
             ParsingEdgeError::WeightValueMustBeInteger("10a0".to_owned());
             "error_parsing_edge_non_integer_weight"
 )]
-#[test_case("error_parsing_edge_empty_line", 2,
-            ParsingEdgeError::EmptyLine;
-            "error_creating_edge_empty_line"
+#[test_case("error_parsing_edge_unexpected_trailing_token", 5,
+            ParsingEdgeError::UnexpectedTrailingToken("40".to_owned());
+            "error_parsing_edge_unexpected_trailing_token"
 )]
-#[test_case("error_parsing_edge_missing_to_index", 4,
+#[test_case("error_parsing_edge_missing_to_index", 5,
             ParsingEdgeError::MissingToIndexField;
             "error_creating_edge_missing_to_index"
 )]
-#[test_case("error_parsing_edge_missing_weight", 3,
+#[test_case("error_parsing_edge_missing_weight", 4,
             ParsingEdgeError::MissingWeightField;
             "error_edge_description_missing_weight"
 )]
-#[test_case("error_adding_edge_too_many_edges", 4,
+#[test_case("error_adding_edge_too_many_edges", 5,
             AddingEdgeError::TooManyEdges{
                 edges_count: 3,
-                edge: Edge{ from_index: 1, to_index: 4, weight: 200 }
+                edge: Edge{ from_index: 1, to_index: 4, weight: 200 }.to_string()
             };
             "error_adding_edge_too_many_edges"
 )]
-#[test_case("error_adding_edge_wrong_from_index", 3,
+#[test_case("error_adding_edge_wrong_from_index", 4,
             AddingEdgeError::WrongFromIndex{
                 nodes_count: 4,
-                edge: Edge{ from_index: 5, to_index: 3, weight: 100, },
+                edge: Edge{ from_index: 5, to_index: 3, weight: 100, }.to_string(),
             };
             "error_adding_edge_wrong_from_index"
 )]
-#[test_case("error_adding_edge_wrong_to_index", 2,
+#[test_case("error_adding_edge_wrong_to_index", 3,
             AddingEdgeError::WrongToIndex{
                 nodes_count: 3,
-                edge: Edge{ from_index: 1, to_index: 4, weight: 100, },
+                edge: Edge{ from_index: 1, to_index: 4, weight: 100, }.to_string(),
             };
             "error_adding_edge_wrong_to_index"
 )]
+#[test_case("error_adding_edge_too_many_node_labels", 4,
+            AddingEdgeError::TooManyNodeLabels{
+                edge: "fountain garage 60".to_owned(),
+                label: "garage".to_owned(),
+                nodes_count: 3,
+            };
+            "error_adding_edge_too_many_node_labels"
+)]
 fn edge_errors<E: Into<BuildGraphError>>(graph_file: &str, expected_line_no_with_error: usize, expected_error: E) {
-    let expected_error = expected_error.into();
+    validate_line_of_graph_file(
+        function!(),
+        graph_file,
+        expected_line_no_with_error,
+        expected_error.into(),
+    );
+}
 
-    let path = build_path(function!(), graph_file);
-    let actual_error = build_graph(&path).unwrap_err();
+// -----------------------------------------------------------------------------
+// When lines of DIMACS file are invalid
+// -----------------------------------------------------------------------------
+
+#[test_case("error_dimacs_missing_problem_line", 2,
+            DimacsParsingError::MissingProblemLine;
+            "error_dimacs_missing_problem_line"
+)]
+#[test_case("error_dimacs_unexpected_problem_line", 3,
+            DimacsParsingError::UnexpectedProblemLine;
+            "error_dimacs_unexpected_problem_line"
+)]
+#[test_case("error_dimacs_unsupported_problem", 2,
+            DimacsParsingError::UnsupportedProblem("max".to_owned());
+            "error_dimacs_unsupported_problem"
+)]
+#[test_case("error_dimacs_invalid_problem_line", 1,
+            DimacsParsingError::InvalidProblemLine("p sp 2".to_owned());
+            "error_dimacs_invalid_problem_line"
+)]
+#[test_case("error_dimacs_unknown_line_descriptor", 3,
+            DimacsParsingError::UnknownLineDescriptor("n".to_owned());
+            "error_dimacs_unknown_line_descriptor"
+)]
+#[test_case("error_dimacs_wrong_to_index", 4,
+            AddingEdgeError::WrongToIndex{
+                nodes_count: 2,
+                edge: Edge{ from_index: 1, to_index: 3, weight: 10, }.to_string(),
+            };
+            "error_dimacs_wrong_to_index"
+)]
+fn dimacs_errors<E: Into<BuildGraphError>>(graph_file: &str, expected_line_no_with_error: usize, expected_error: E) {
+    validate_line_of_graph_file(
+        function!(),
+        graph_file,
+        expected_line_no_with_error,
+        expected_error.into(),
+    );
+}
+
+fn validate_line_of_graph_file(
+    dir_name: &str,
+    graph_file: &str,
+    expected_line_no_with_error: usize,
+    expected_error: BuildGraphError,
+) {
+    let path = build_path(dir_name, graph_file);
+    let actual_error = build_graph::<i32, _>(&path).unwrap_err();
 
     if let BuildGraphError::ErrorInGraphDescriptionFile {
         line_no: actual_line_no_with_error,
@@ -132,6 +204,7 @@ fn edge_errors<E: Into<BuildGraphError>>(graph_file: &str, expected_line_no_with
 
 #[test_case("error_graph_not_connected", BuildGraphError::GraphNotConnected; "error_graph_not_connected")]
 #[test_case("error_too_few_edges", BuildGraphError::TooFewEdges{current_count: 3, declared: 4}; "error_too_few_edges")]
+#[test_case("error_directed_graph_not_strongly_connected", BuildGraphError::GraphNotConnected; "error_directed_graph_not_strongly_connected")]
 fn graph_building_errors(graph_file: &str, expected_error: BuildGraphError) {
     validate_graph_file(function!(), graph_file, expected_error);
 }
@@ -0,0 +1,123 @@
+use graph::{
+    Edge,
+    Graph,
+    GraphBuilder,
+    GraphKind,
+    GraphParameters,
+    NodeLabels,
+    OrderedFloat,
+    ValidationPolicy,
+    Weight,
+};
+use proptest::prelude::*;
+
+const MAX_NODES_COUNT: u32 = 20;
+
+// edges of a spanning tree (every node is connected with some node of lower index) and random extra edges,
+// so the graph is connected
+fn connected_edges<W, S>(weight: S) -> impl Strategy<Value = (u32, Vec<Edge<W>>)>
+where
+    W: Weight,
+    S: Strategy<Value = W> + Clone,
+{
+    (1..=MAX_NODES_COUNT).prop_flat_map(move |nodes_count| {
+        let tree_edges = (2..=nodes_count)
+            .map(|to_index| (1..to_index, weight.clone()).prop_map(move |(from, w)| Edge::new(from, to_index, w)))
+            .collect::<Vec<_>>();
+        let extra_edges = prop::collection::vec(
+            (1..=nodes_count, 1..=nodes_count, weight.clone()).prop_map(|(from, to, w)| Edge::new(from, to, w)),
+            0..10,
+        );
+
+        (Just(nodes_count), tree_edges, extra_edges).prop_map(|(nodes_count, mut edges, extra_edges)| {
+            edges.extend(extra_edges);
+            (nodes_count, edges)
+        })
+    })
+}
+
+// directed graph is made strongly connected by adding edges going back to the first node
+fn build<W: Weight>(nodes_count: u32, mut edges: Vec<Edge<W>>, kind: GraphKind, labelled: bool) -> Graph<W> {
+    if kind == GraphKind::Directed {
+        let back_edges: Vec<_> = (2..=nodes_count)
+            .map(|index| Edge::new(index, 1, edges[0].weight))
+            .collect();
+        edges.extend(back_edges);
+    }
+
+    let mut graph_parameters = GraphParameters::new(nodes_count, edges.len()).with_kind(kind);
+    if labelled {
+        graph_parameters = graph_parameters.with_labels();
+    }
+
+    let mut graph_builder = GraphBuilder::new(graph_parameters);
+    for edge in edges {
+        if labelled {
+            let label = |index| format!("node_{}", index);
+            graph_builder
+                .add_labelled_edge(&label(edge.from_index), &label(edge.to_index), edge.weight)
+                .unwrap();
+        } else {
+            graph_builder.add_edge(edge).unwrap();
+        }
+    }
+
+    graph_builder.build().unwrap()
+}
+
+fn kind() -> impl Strategy<Value = GraphKind> {
+    prop_oneof![Just(GraphKind::Undirected), Just(GraphKind::Directed)]
+}
+
+proptest! {
+    #[test]
+    fn integer_weights(
+        (nodes_count, edges) in connected_edges(any::<i32>()),
+        kind in kind(),
+        labelled in any::<bool>(),
+    ) {
+        let graph = build(nodes_count, edges, kind, labelled);
+
+        prop_assert_eq!(graph.to_string().parse::<Graph>().unwrap(), graph);
+    }
+
+    #[test]
+    fn float_weights(
+        (nodes_count, edges) in connected_edges((-1e9..1e9).prop_map(OrderedFloat)),
+        kind in kind(),
+    ) {
+        let graph = build(nodes_count, edges, kind, false);
+
+        prop_assert_eq!(graph.to_string().parse::<Graph<OrderedFloat>>().unwrap(), graph);
+    }
+
+    // graph built without connectivity check is read back with the default policy
+    #[test]
+    fn disconnected_graphs(
+        edges in prop::collection::vec(
+            (1..=MAX_NODES_COUNT, 1..=MAX_NODES_COUNT, any::<i32>()).prop_map(|(from, to, w)| Edge::new(from, to, w)),
+            0..10,
+        ),
+        kind in kind(),
+    ) {
+        let mut graph_builder = GraphBuilder::new(GraphParameters::new(MAX_NODES_COUNT, edges.len()).with_kind(kind))
+            .with_validation_policy(ValidationPolicy::permissive());
+        for edge in edges {
+            graph_builder.add_edge(edge).unwrap();
+        }
+        let graph = graph_builder.build().unwrap();
+
+        prop_assert_eq!(graph.to_string().parse::<Graph>().unwrap(), graph);
+    }
+}
+
+#[test]
+fn labels_not_in_order_of_appearance_are_written_as_indices() {
+    let mut labels = NodeLabels::new();
+    labels.insert("pond");
+    labels.insert("gate");
+
+    let graph: Graph = Graph::new(2, vec![Edge::new(2, 1, 100)], GraphKind::Undirected).with_labels(labels);
+
+    assert_eq!(graph.to_string(), "2 1\n2 1 100\n");
+}
@@ -30,6 +30,9 @@ pub enum SubCommand {
     /// Runs algorithm using data from chosen file
     #[clap(visible_alias = "ra")]
     RunAlgorithm(RunAlgorithmArgs),
+    /// Writes graph from chosen file without self-loops and parallel edges
+    #[clap(visible_alias = "ngf")]
+    NormalizeGraphFile(NormalizeGraphFileArgs),
 }
 
 impl SubCommand {
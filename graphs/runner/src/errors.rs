@@ -1,5 +1,5 @@
 use algorithms::AlgorithmError;
-use graph::BuildGraphError;
+use graph::{BuildGraphError, Diagnostics, NormalizationError};
 use parse_display::Display;
 use std::io::Error as ioError;
 use thiserror::Error;
@@ -51,6 +51,30 @@ pub enum RunnerError {
 
     #[error("algorithm error - {0}")]
     AlgorithmError(AlgorithmError),
+
+    /// Couldn't write graph to DOT file given in `--emit-dot` option
+    #[error("writing DOT file failed with error - {0}")]
+    EmitDotError(ioError),
+
+    /// Task file read with `--all-errors` option is invalid, all found errors are listed
+    #[error("invalid task file\n{0}")]
+    InvalidTaskFile(Diagnostics),
+
+    /// `--all-errors` option was given for task file in other format than native
+    #[error("`--all-errors` option can be used only with `native` format")]
+    AllErrorsNotSupported,
+
+    /// Couldn't write graph to the output file
+    #[error("writing output file failed with error - {0}")]
+    WritingOutputError(ioError),
+
+    /// Node given in command line arguments isn't a label (or index, if graph isn't labelled) of any node
+    #[error("there is no node `{0}` in the graph")]
+    UnknownNode(String),
+
+    /// Graph read by `normalize-graph-file` couldn't be normalized
+    #[error("graph normalization error - {0}")]
+    NormalizationError(NormalizationError),
 }
 
 impl From<BuildGraphError> for RunnerError {
@@ -71,6 +95,12 @@ impl From<AlgorithmError> for RunnerError {
     }
 }
 
+impl From<NormalizationError> for RunnerError {
+    fn from(e: NormalizationError) -> Self {
+        RunnerError::NormalizationError(e)
+    }
+}
+
 // -----------------------------------------------------------------------------
 
 /// Enum containing variants of errors that might occur during generating graph
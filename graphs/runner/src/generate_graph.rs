@@ -1,11 +1,13 @@
 use crate::errors::{GenerateGraphError, GenerateGraphResult as Result};
 use crate::GenerateGraphFileArgs;
+use graph::{write_binary, write_dimacs, Edge, Graph, GraphFormat, GraphKind};
 use rand::prelude::*;
+use std::convert::TryFrom;
 use std::fs::{create_dir_all, File};
-use std::io::{Result as ioResult, Write};
+use std::io::{BufWriter, Result as ioResult, Write};
 use std::path::Path;
 
-/// Generates txt file containing multi-graph data using `parameters`
+/// Generates file containing multi-graph data using `parameters`
 ///
 /// Graph file is generated using [`rand`], so two nodes might be connected by more than one edge
 ///
@@ -13,11 +15,12 @@ use std::path::Path;
 /// so function checks if it's possible to generate connected graph from given parameters.
 /// If it's not, function returns an error.
 ///
-/// First line in the file contains `nodes_count` and `edges_count`
-/// Then, file contains `nodes_count - 1` lines describing edges
-/// connecting first node with every other node, so graph is for sure connected
+/// First `nodes_count - 1` edges connect first node with every other node, so graph is for sure connected
 /// After that, the rest of the edges is generated randomly.
 ///
+/// Graph is written in format given in `--format` option. In [`GraphFormat::Native`] format,
+/// first line in the file contains `nodes_count` and `edges_count`, then every line describes one edge.
+///
 /// # Example
 /// ```
 /// use runner::{GenerateGraphFileArgs, generate_graph};
@@ -37,7 +40,7 @@ use std::path::Path;
 /// assert!(output_graph_file.path().exists());
 ///
 /// // check if generated graph is correct
-/// let build_result = build_graph(output_graph_file.path());
+/// let build_result = build_graph::<i32, _>(output_graph_file.path());
 /// assert!(build_result.is_ok());
 /// ```
 ///
@@ -62,37 +65,63 @@ pub fn generate_graph(parameters: &GenerateGraphFileArgs) -> Result<()> {
 
     create_directory_if_necessary(&parameters.graph_file).map_err(GenerateGraphError::CreatingDirectoryError)?;
 
-    let mut output = File::create(&parameters.graph_file).map_err(GenerateGraphError::CreatingFileError)?;
+    let output = File::create(&parameters.graph_file).map_err(GenerateGraphError::CreatingFileError)?;
+
+    let graph = generate_edges(parameters, edges_left);
+
+    write_graph(&graph, parameters.format, BufWriter::new(output)).map_err(GenerateGraphError::WritingError)
+}
 
-    // write `nodes_count` and `edges_count` to the first line of file
-    output
-        .write_all(format!("{} {}\n", parameters.nodes_count, parameters.edges_count).as_ref())
-        .map_err(GenerateGraphError::WritingError)?;
+/// Generates connected multi-graph with random weights
+///
+/// # Arguments
+///
+/// * `parameters` - parameters of the graph
+/// * `edges_left` - number of random edges added after connecting first node with every other node
+fn generate_edges(parameters: &GenerateGraphFileArgs, edges_left: u32) -> Graph {
+    let nodes_count = parameters.nodes_count.value();
+    // weights of the graph are `i32`, so bigger max weight is truncated
+    let max_weight = i32::try_from(parameters.max_weight.value()).unwrap_or(i32::MAX);
 
     let mut rng = thread_rng();
+    let mut edges = Vec::with_capacity(parameters.edges_count.value() as usize);
 
     // add edges connecting first node with every other node so the graph will be connected
-    for i in 2..=parameters.nodes_count.value() {
-        output
-            .write_all(format!("1 {} {}\n", i, rng.gen_range(1..=parameters.max_weight.value())).as_ref())
-            .map_err(GenerateGraphError::WritingError)?;
+    for i in 2..=nodes_count {
+        edges.push(Edge::new(1, i, rng.gen_range(1..=max_weight)));
     }
 
     // generate rest of edges using `rng`
     for _ in 0..edges_left {
-        output
-            .write_all(
-                format!(
-                    "{} {} {}\n",
-                    rng.gen_range(1..=parameters.nodes_count.value()),
-                    rng.gen_range(1..=parameters.nodes_count.value()),
-                    rng.gen_range(1..=parameters.max_weight.value())
-                )
-                .as_ref(),
-            )
-            .map_err(GenerateGraphError::WritingError)?;
+        edges.push(Edge::new(
+            rng.gen_range(1..=nodes_count),
+            rng.gen_range(1..=nodes_count),
+            rng.gen_range(1..=max_weight),
+        ));
+    }
+
+    Graph::new(nodes_count, edges, GraphKind::Undirected)
+}
+
+/// Writes graph in given format
+///
+/// # Arguments
+///
+/// * `graph` - graph that will be written
+/// * `format` - format of the output
+/// * `output` - destination of the output
+pub(crate) fn write_graph<Wr: Write>(graph: &Graph, format: GraphFormat, mut output: Wr) -> ioResult<()> {
+    match format {
+        GraphFormat::Native => {
+            write!(output, "{}", graph)?;
+            output.flush()
+        }
+        GraphFormat::Dimacs => {
+            write_dimacs(graph, &mut output)?;
+            output.flush()
+        }
+        GraphFormat::Binary => write_binary(graph, output),
     }
-    Ok(())
 }
 
 /// Takes a reference to a filepath and creates directory specified in the path if it doesn't exist
@@ -102,7 +131,7 @@ pub fn generate_graph(parameters: &GenerateGraphFileArgs) -> Result<()> {
 /// # Arguments
 ///
 /// * `path` - path to file
-fn create_directory_if_necessary(path: &Path) -> ioResult<()> {
+pub(crate) fn create_directory_if_necessary(path: &Path) -> ioResult<()> {
     let mut path = path.to_path_buf();
     path.pop();
     create_dir_all(path)
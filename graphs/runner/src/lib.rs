@@ -1,9 +1,11 @@
 mod cli;
 mod errors;
 mod generate_graph;
+mod normalize_graph;
 mod subcommands;
 
 pub use cli::*;
 pub use errors::{GenerateGraphError, GenerateGraphResult, RunnerError, RunnerResult};
 pub use generate_graph::generate_graph;
+pub use normalize_graph::normalize_graph;
 pub use subcommands::*;
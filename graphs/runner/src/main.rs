@@ -1,5 +1,8 @@
-use algorithms::{calculate_min_total_weight, find_shortest_path_length};
+use algorithms::{find_min_spanning_tree, find_shortest_path};
+use graph::DotWriter;
 use runner::*;
+use std::fs::File;
+use std::io::{BufWriter, Write};
 use utils::ApplicationRunner;
 
 /// Main function that is called when the app starts
@@ -19,19 +22,47 @@ impl ApplicationRunner for App {
     fn run(&self, cmd_args: CmdArgs) -> RunnerResult<()> {
         match cmd_args.subcommand {
             SubCommand::RunAlgorithm(task_data) => {
-                let graph = graph::build_graph(&task_data.task_file)?;
-                match task_data.algorithm_args {
-                    AlgorithmArgs::Kruskals { .. } => println!("{}", calculate_min_total_weight(graph)),
+                let graph: graph::Graph = task_data.build_graph()?;
+                let dot_writer = match &task_data.algorithm_args {
+                    AlgorithmArgs::Kruskals { .. } => {
+                        let tree = find_min_spanning_tree(&graph)?;
+                        println!("{}", tree.total_weight);
+                        DotWriter::new(&graph).with_bold_edges(tree.edges)
+                    }
                     AlgorithmArgs::Dijkstras { start_node, end_node } => {
-                        println!("{}", find_shortest_path_length(&graph, start_node, end_node)?)
+                        let start_node = node_index(&graph, start_node)?;
+                        let end_node = node_index(&graph, end_node)?;
+                        let path = find_shortest_path(&graph, start_node, end_node)?;
+                        println!("{}", path.length);
+                        DotWriter::new(&graph).with_path(&path.nodes)
                     }
                 };
+
+                if let Some(dot_file) = &task_data.emit_dot {
+                    File::create(dot_file)
+                        .and_then(|file| {
+                            let mut writer = BufWriter::new(file);
+                            dot_writer.write(&mut writer)?;
+                            writer.flush()
+                        })
+                        .map_err(RunnerError::EmitDotError)?;
+                }
             }
 
             SubCommand::GenerateGraphFile(params) => {
                 generate_graph(&params)?;
                 println!("Graph file with path {:?} successfully generated!", params.graph_file);
             }
+
+            SubCommand::NormalizeGraphFile(params) => {
+                let report = normalize_graph(&params)?;
+                println!(
+                    "Removed {} self-loops and {} parallel edges, graph file with path {:?} successfully written!",
+                    report.self_loops.len(),
+                    report.merged_edges.len(),
+                    params.graph_file
+                );
+            }
         }
         Ok(())
     }
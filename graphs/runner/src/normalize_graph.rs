@@ -0,0 +1,55 @@
+use crate::errors::{RunnerError, RunnerResult as Result};
+use crate::generate_graph::{create_directory_if_necessary, write_graph};
+use crate::NormalizeGraphFileArgs;
+use graph::{build_graph_with_options, Graph, NormalizationReport, ReadOptions, ValidationPolicy};
+use std::fs::File;
+use std::io::BufWriter;
+
+/// Reads graph from the task file, removes its self-loops and merges parallel edges (see [`graph::Graph::normalize`])
+/// and writes the simple graph to the output file
+///
+/// Graph is read without any validation apart from checking its edges, so it doesn't have to be connected.
+/// Returns [`NormalizationReport`] listing removed edges.
+///
+/// # Example
+/// ```
+/// use runner::{normalize_graph, NormalizeGraphFileArgs};
+/// use std::io::Write;
+/// use tempfile::NamedTempFile;
+/// use graph::{build_graph, Graph};
+///
+/// let mut task_file = NamedTempFile::new().unwrap();
+/// write!(task_file, "3 5\n1 2 13\n1 3 1\n2 3 1\n1 1 11\n1 3 2\n").unwrap();
+/// let output_graph_file = NamedTempFile::new().unwrap();
+///
+/// let args = format!("--task-file {} --graph-file {} --merge max",
+///     task_file.path().to_str().unwrap(),
+///     output_graph_file.path().to_str().unwrap())
+///     .parse::<NormalizeGraphFileArgs>().unwrap();
+///
+/// let report = normalize_graph(&args).unwrap();
+/// assert_eq!(report.self_loops.len(), 1);
+/// assert_eq!(report.merged_edges.len(), 1);
+///
+/// let graph: Graph = build_graph(output_graph_file.path()).unwrap();
+/// assert_eq!(graph.to_string(), "3 3\n1 2 13\n1 3 2\n2 3 1\n");
+/// ```
+///
+/// # Arguments
+///
+/// * `args` - task file, output file and merge rule
+pub fn normalize_graph(args: &NormalizeGraphFileArgs) -> Result<NormalizationReport> {
+    let mut options = ReadOptions::new().with_policy(ValidationPolicy::permissive());
+    if let Some(format) = args.format {
+        options = options.with_format(format);
+    }
+
+    let mut graph: Graph = build_graph_with_options(args.task_file.data_source(), options)?;
+    let report = graph.normalize(args.merge)?;
+
+    create_directory_if_necessary(&args.graph_file).map_err(RunnerError::WritingOutputError)?;
+    let output = File::create(&args.graph_file).map_err(RunnerError::WritingOutputError)?;
+    write_graph(&graph, args.output_format, BufWriter::new(output)).map_err(RunnerError::WritingOutputError)?;
+
+    Ok(report)
+}
@@ -1,5 +1,6 @@
 use clap::{AppSettings, Clap};
 use core::result::Result::Ok;
+use graph::GraphFormat;
 use std::path::PathBuf;
 
 use crate::{RunnerError, SubCommand};
@@ -26,6 +27,10 @@ pub struct GenerateGraphFileArgs {
     /// Maximum weight of an edge in graph (must be an positive integer)
     #[clap(long, short)]
     pub max_weight: PositiveInteger,
+
+    /// Format of the output file (`native`, `dimacs` or `binary`)
+    #[clap(long, default_value = "native")]
+    pub format: GraphFormat,
 }
 
 impl FromStr for GenerateGraphFileArgs {
@@ -1,5 +1,7 @@
 mod generate_graph_file;
+mod normalize_graph_file;
 mod run_algorithm;
 
 pub use generate_graph_file::*;
+pub use normalize_graph_file::*;
 pub use run_algorithm::*;
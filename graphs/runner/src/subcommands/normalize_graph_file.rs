@@ -0,0 +1,42 @@
+use crate::{RunnerError, SubCommand, TaskFile};
+use clap::{AppSettings, Clap};
+use graph::{GraphFormat, MergeRule};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Subcommand turning graph from `task_file` into simple graph (e.g. generated multi-graph into benchmark input)
+#[derive(Clap, Debug)]
+#[clap(setting = AppSettings::ColoredHelp)]
+pub struct NormalizeGraphFileArgs {
+    /// Name of file containing graph data (`-` means standard input)
+    #[clap(long, short)]
+    pub task_file: TaskFile,
+
+    /// Format of the task file (`native`, `dimacs` or `binary`), detected if not given
+    #[clap(long)]
+    pub format: Option<GraphFormat>,
+
+    /// Output filename
+    #[clap(long, short)]
+    pub graph_file: PathBuf,
+
+    /// Format of the output file (`native`, `dimacs` or `binary`)
+    #[clap(long, default_value = "native")]
+    pub output_format: GraphFormat,
+
+    /// Weight of the edge replacing parallel edges - the smallest (`min`), the greatest (`max`) or their sum (`sum`)
+    #[clap(long, short, default_value = "min")]
+    pub merge: MergeRule,
+}
+
+impl FromStr for NormalizeGraphFileArgs {
+    type Err = RunnerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match SubCommand::try_from_name_and_args("normalize-graph-file", s)? {
+            SubCommand::NormalizeGraphFile(cmd) => Ok(cmd),
+            // args not matching normalize-graph-file arguments are rejected by `SubCommand::try_from_name_and_args`
+            _ => panic!("this should never happen !"),
+        }
+    }
+}
@@ -1,19 +1,74 @@
+use crate::{RunnerError, RunnerResult};
 use clap::{AppSettings, Clap};
+use graph::{
+    build_graph_with_diagnostics,
+    build_graph_with_options,
+    DataSource,
+    Graph,
+    GraphFormat,
+    ReadOptions,
+    ValidationPolicy,
+    Weight,
+};
+use std::io::{self, Error as IoError};
+use std::path::PathBuf;
+use std::str::FromStr;
 use utils::{PathBufWithFileThatMustExist, PositiveInteger};
 
 /// Subcommand running one of the available algorithms for graph built from `task_file`
 #[derive(Clap, Debug)]
 #[clap(setting = AppSettings::ColoredHelp)]
 pub struct RunAlgorithmArgs {
-    /// Name of file containing graph data
+    /// Name of file containing graph data (`-` means standard input)
     #[clap(long, short)]
-    pub task_file: PathBufWithFileThatMustExist,
+    pub task_file: TaskFile,
+
+    /// Format of the task file (`native`, `dimacs` or `binary`), detected if not given
+    #[clap(long)]
+    pub format: Option<GraphFormat>,
+
+    /// Allows task file in `native` format without the first line (graph parameters), so it's a plain list of edges
+    #[clap(long)]
+    pub optional_header: bool,
+
+    /// Reports all errors found in the task file instead of the first one (task file must be in `native` format)
+    #[clap(long)]
+    pub all_errors: bool,
+
+    /// Name of file the graph will be written to in Graphviz DOT language, with algorithm result highlighted
+    #[clap(long)]
+    pub emit_dot: Option<PathBuf>,
 
     /// Algorithm name
     #[clap(subcommand)]
     pub algorithm_args: AlgorithmArgs,
 }
 
+impl RunAlgorithmArgs {
+    /// Builds graph from the task file, using given format or detecting it
+    ///
+    /// Graph doesn't have to be connected, algorithms check their own preconditions.
+    /// With `--all-errors` returns [`RunnerError::InvalidTaskFile`] listing all invalid lines.
+    pub fn build_graph<W: Weight>(&self) -> RunnerResult<Graph<W>> {
+        let mut options = ReadOptions::new()
+            .with_policy(ValidationPolicy::new().with_connectivity(false))
+            .with_optional_header(self.optional_header);
+        if let Some(format) = self.format {
+            options = options.with_format(format);
+        }
+
+        if self.all_errors {
+            if !matches!(self.format, None | Some(GraphFormat::Native)) {
+                return Err(RunnerError::AllErrorsNotSupported);
+            }
+            return build_graph_with_diagnostics(self.task_file.data_source(), options)
+                .map_err(RunnerError::InvalidTaskFile);
+        }
+
+        Ok(build_graph_with_options(self.task_file.data_source(), options)?)
+    }
+}
+
 /// Available algorithms and their args (if necessary)
 #[derive(Clap, Debug)]
 pub enum AlgorithmArgs {
@@ -22,11 +77,87 @@ pub enum AlgorithmArgs {
     Kruskals {},
 
     /// Calculates shortest path weight from `start_node` to `end_node`
+    /// (nodes are given by their labels if the graph has them)
     #[clap(visible_alias = "d")]
     Dijkstras {
         #[clap(long, short)]
-        start_node: PositiveInteger,
+        start_node: String,
         #[clap(long, short)]
-        end_node:   PositiveInteger,
+        end_node:   String,
     },
 }
+
+/// File containing graph data or standard input (given as `-`)
+///
+/// # Example
+/// ```
+/// use runner::TaskFile;
+///
+/// assert!(matches!("-".parse::<TaskFile>(), Ok(TaskFile::Stdin)));
+/// assert!("non_existing_file.txt".parse::<TaskFile>().is_err());
+/// ```
+#[derive(Debug)]
+pub enum TaskFile {
+    /// Graph data is read from standard input
+    Stdin,
+
+    /// Graph data is read from existing file
+    File(PathBufWithFileThatMustExist),
+}
+
+impl TaskFile {
+    /// Returns [`graph::DataSource`] the graph should be built from
+    ///
+    /// Both file and standard input are read line by line
+    pub fn data_source(&self) -> DataSource<'_> {
+        match self {
+            TaskFile::Stdin => DataSource::from_reader(io::stdin().lock()),
+            TaskFile::File(path) => DataSource::from(path),
+        }
+    }
+}
+
+impl FromStr for TaskFile {
+    type Err = IoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "-" => Ok(TaskFile::Stdin),
+            path => path.parse().map(TaskFile::File),
+        }
+    }
+}
+
+/// Finds index of the node given in command line arguments
+///
+/// If the graph is labelled, `node` is the label of the node, otherwise it's the node index.
+/// Returns [`RunnerError::UnknownNode`] if there is no such label or `node` isn't a positive integer.
+///
+/// # Example
+/// ```
+/// use graph::Graph;
+/// use runner::node_index;
+///
+/// let graph: Graph = "2 1 labels
+///     gate pond_north 120"
+///     .parse()
+///     .unwrap();
+///
+/// assert_eq!(node_index(&graph, "pond_north").unwrap(), 2);
+/// assert!(node_index(&graph, "garage").is_err());
+/// ```
+///
+/// # Arguments
+///
+/// * `graph` - graph containing the node
+/// * `node` - label or index of the node
+pub fn node_index<W: Weight>(graph: &Graph<W>, node: &str) -> RunnerResult<PositiveInteger> {
+    let index = match graph.labels() {
+        Some(labels) => labels.index(node),
+        None => node.parse::<PositiveInteger>().ok().map(|index| index.value()),
+    };
+
+    index
+        .map(PositiveInteger::new)
+        .ok_or_else(|| RunnerError::UnknownNode(node.to_owned()))
+}
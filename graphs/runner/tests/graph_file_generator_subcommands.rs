@@ -50,14 +50,15 @@ mod failing_tests {
     #[test_case("--graph-file aaa.txt"; "3")]
     #[test_case(""; "4")]
     fn missing_required_options(args: &str) {
-        validate_args(&args)
+        validate_args(args)
     }
 }
 
 mod passing_tests {
     use super::*;
-    use graph::build_graph;
+    use graph::{build_graph, build_graph_in_format, BuildGraphResult, Graph, GraphFormat, GraphKind};
     use tempfile::NamedTempFile;
+    use test_case::test_case;
 
     #[test]
     fn ok() -> Result<()> {
@@ -74,18 +75,44 @@ mod passing_tests {
         assert!(result.is_ok());
         assert!(output_graph_file.path().exists());
 
-        let graph_result = build_graph(output_graph_file.path());
+        let graph_result = build_graph::<i32, _>(output_graph_file.path());
 
         // temp_dir is deleted even if the tests panics so I use this if statement to keep it
-        if !graph_result.is_ok() {
+        if let Err(error) = graph_result {
             // keep temp_dir as a normal directory
             output_graph_file
                 .into_temp_path()
                 .persist("./passing_tests_ok_test_graph_file.txt")?;
             // panic because of an error
-            panic!("{:?}", graph_result.unwrap_err());
+            panic!("{:?}", error);
         }
 
         Ok(())
     }
+
+    #[test_case(GraphFormat::Native, GraphKind::Undirected; "native")]
+    #[test_case(GraphFormat::Dimacs, GraphKind::Directed; "dimacs")]
+    #[test_case(GraphFormat::Binary, GraphKind::Undirected; "binary")]
+    fn output_format(format: GraphFormat, expected_kind: GraphKind) -> Result<()> {
+        let output_graph_file = NamedTempFile::new()?;
+
+        let parameters = format!(
+            "--graph-file {} --nodes-count 5 --edges-count 8 --max-weight 100 --format {}",
+            output_graph_file.path().to_str().unwrap(),
+            format
+        )
+        .parse::<GenerateGraphFileArgs>()?;
+
+        generate_graph(&parameters).map_err(RunnerError::from)?;
+
+        // format is detected and it's the same as the given one
+        let detected: BuildGraphResult<Graph> = build_graph(output_graph_file.path());
+        let graph: Graph = build_graph_in_format(output_graph_file.path(), format)?;
+
+        assert_eq!(detected?.edges(), graph.edges());
+        assert_eq!(graph.nodes_count(), 5);
+        assert_eq!(graph.kind(), expected_kind);
+
+        Ok(())
+    }
 }
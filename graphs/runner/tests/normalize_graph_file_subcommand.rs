@@ -0,0 +1,84 @@
+use runner::*;
+
+use anyhow::Result;
+use graph::{build_graph, Graph, MergeRule, NormalizationError};
+use std::io::Write;
+use tempfile::NamedTempFile;
+use test_case::test_case;
+
+#[test]
+fn generated_graph_becomes_simple() -> Result<()> {
+    let generated_graph_file = NamedTempFile::new()?;
+    let normalized_graph_file = NamedTempFile::new()?;
+
+    let parameters = format!(
+        "--graph-file {} --nodes-count 4 --edges-count 30 --max-weight 100",
+        generated_graph_file.path().to_str().unwrap()
+    )
+    .parse::<GenerateGraphFileArgs>()?;
+    generate_graph(&parameters).map_err(RunnerError::from)?;
+
+    let args = format!(
+        "--task-file {} --graph-file {} --output-format binary",
+        generated_graph_file.path().to_str().unwrap(),
+        normalized_graph_file.path().to_str().unwrap()
+    )
+    .parse::<NormalizeGraphFileArgs>()?;
+    let report = normalize_graph(&args)?;
+
+    let mut graph: Graph = build_graph(normalized_graph_file.path())?;
+
+    assert_eq!(graph.edges().len() + report.removed_count(), 30);
+    assert!(graph.normalize(MergeRule::Min)?.is_empty());
+
+    Ok(())
+}
+
+#[test_case("min" => MergeRule::Min; "min")]
+#[test_case("sum" => MergeRule::Sum; "sum")]
+fn merge_rule(rule: &str) -> MergeRule {
+    let mut task_file = NamedTempFile::new().unwrap();
+    writeln!(task_file, "2 1\n1 2 10").unwrap();
+
+    let args = format!(
+        "--task-file {} --graph-file out.txt --merge {}",
+        task_file.path().to_str().unwrap(),
+        rule
+    );
+
+    args.parse::<NormalizeGraphFileArgs>().unwrap().merge
+}
+
+#[test]
+fn unknown_merge_rule() {
+    let task_file = NamedTempFile::new().unwrap();
+
+    let args = format!(
+        "--task-file {} --graph-file out.txt --merge avg",
+        task_file.path().to_str().unwrap()
+    );
+
+    assert!(args.parse::<NormalizeGraphFileArgs>().is_err());
+}
+
+#[test]
+fn sum_overflow_is_an_error() {
+    let mut task_file = NamedTempFile::new().unwrap();
+    writeln!(task_file, "2 2\n1 2 2147483647\n1 2 10").unwrap();
+    let output_graph_file = NamedTempFile::new().unwrap();
+
+    let args = format!(
+        "--task-file {} --graph-file {} --merge sum",
+        task_file.path().to_str().unwrap(),
+        output_graph_file.path().to_str().unwrap()
+    )
+    .parse::<NormalizeGraphFileArgs>()
+    .unwrap();
+    let expected_error = RunnerError::from(NormalizationError::WeightOverflow {
+        edge: "1 2 10".to_owned(),
+    });
+
+    let actual_error = normalize_graph(&args).unwrap_err();
+
+    assert_eq!(actual_error.to_string(), expected_error.to_string());
+}